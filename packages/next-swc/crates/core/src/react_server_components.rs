@@ -38,10 +38,17 @@ impl Config {
 #[serde(rename_all = "camelCase")]
 pub struct Options {
     pub is_server: bool,
+    /// The module client entries import `createProxy` from on the server.
+    /// Defaults to `private-next-rsc-mod-ref-proxy`.
+    #[serde(default)]
+    pub proxy_import_source: Option<String>,
 }
 
+const DEFAULT_PROXY_IMPORT_SOURCE: &str = "private-next-rsc-mod-ref-proxy";
+
 struct ReactServerComponents<C: Comments> {
     is_server: bool,
+    proxy_import_source: String,
     filepath: String,
     app_dir: Option<PathBuf>,
     comments: C,
@@ -296,7 +303,7 @@ impl<C: Comments> ReactServerComponents<C> {
                         init: Some(Box::new(Expr::Call(CallExpr {
                             span: DUMMY_SP,
                             callee: quote_ident!("require").as_callee(),
-                            args: vec![quote_str!("private-next-rsc-mod-ref-proxy").as_arg()],
+                            args: vec![quote_str!(&*self.proxy_import_source).as_arg()],
                             type_args: Default::default(),
                         }))),
                         definite: false,
@@ -557,12 +564,14 @@ pub fn server_components<C: Comments>(
     comments: C,
     app_dir: Option<PathBuf>,
 ) -> impl Fold + VisitMut {
-    let is_server: bool = match config {
-        Config::WithOptions(x) => x.is_server,
-        _ => true,
+    let (is_server, proxy_import_source) = match config {
+        Config::WithOptions(x) => (x.is_server, x.proxy_import_source),
+        _ => (true, None),
     };
     as_folder(ReactServerComponents {
         is_server,
+        proxy_import_source: proxy_import_source
+            .unwrap_or_else(|| DEFAULT_PROXY_IMPORT_SOURCE.to_string()),
         comments,
         filepath: filename.to_string(),
         app_dir,
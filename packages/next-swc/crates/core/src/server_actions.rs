@@ -23,6 +23,10 @@ use turbopack_binding::swc::core::{
 pub struct Config {
     pub is_server: bool,
     pub enabled: bool,
+    /// Encrypt the values an inline action closes over before they are sent
+    /// to the client, and decrypt them again when the action is invoked.
+    #[serde(default)]
+    pub encryption: bool,
 }
 
 pub fn server_actions<C: Comments>(
@@ -39,6 +43,7 @@ pub fn server_actions<C: Comments>(
         in_export_decl: false,
         in_default_export_decl: false,
         has_action: false,
+        has_encrypted_bound_args: false,
 
         ident_cnt: 0,
         in_module: true,
@@ -66,6 +71,7 @@ struct ServerActions<C: Comments> {
     in_export_decl: bool,
     in_default_export_decl: bool,
     has_action: bool,
+    has_encrypted_bound_args: bool,
 
     ident_cnt: u32,
     in_module: bool,
@@ -161,6 +167,12 @@ impl<C: Comments> ServerActions<C> {
             }
         });
 
+        let encrypt_bound_args = self.config.encryption && !ids_from_closure.is_empty();
+        if encrypt_bound_args {
+            self.has_encrypted_bound_args = true;
+        }
+        let action_id = generate_action_id(self.file_name.to_string(), export_name.to_string());
+
         let args_arg = private_ident!("args");
 
         let call = CallExpr {
@@ -211,11 +223,7 @@ impl<C: Comments> ServerActions<C> {
             annotate_ident_as_action(
                 &mut arrow_annotations,
                 ident.clone(),
-                ids_from_closure
-                    .iter()
-                    .cloned()
-                    .map(|id| Some(id.as_arg()))
-                    .collect(),
+                bound_args(&ids_from_closure, &action_id, encrypt_bound_args),
                 self.file_name.to_string(),
                 export_name.to_string(),
                 Some(action_ident.clone()),
@@ -227,6 +235,23 @@ impl<C: Comments> ServerActions<C> {
                 });
             }
 
+            let mut action_body = a.body.clone();
+            if encrypt_bound_args {
+                let stmts = match *action_body {
+                    BlockStmtOrExpr::BlockStmt(block) => block.stmts,
+                    BlockStmtOrExpr::Expr(expr) => vec![Stmt::Return(ReturnStmt {
+                        span: DUMMY_SP,
+                        arg: Some(expr),
+                    })],
+                };
+                action_body = Box::new(BlockStmtOrExpr::BlockStmt(BlockStmt {
+                    span: DUMMY_SP,
+                    stmts: std::iter::once(decrypt_bound_args(&action_id, ids_from_closure.len()))
+                        .chain(stmts)
+                        .collect(),
+                }));
+            }
+
             let new_arrow = ArrowExpr {
                 span: DUMMY_SP,
                 params: vec![
@@ -248,10 +273,16 @@ impl<C: Comments> ServerActions<C> {
             // export const $ACTION_myAction = async () => {}
             let mut new_params: Vec<Pat> = vec![];
 
-            for i in 0..ids_from_closure.len() {
+            if encrypt_bound_args {
                 new_params.push(Pat::Ident(
-                    Ident::new(format!("$$ACTION_ARG_{}", i).into(), DUMMY_SP).into(),
+                    Ident::new("$$ACTION_CLOSURE_BOUND".into(), DUMMY_SP).into(),
                 ));
+            } else {
+                for i in 0..ids_from_closure.len() {
+                    new_params.push(Pat::Ident(
+                        Ident::new(format!("$$ACTION_ARG_{}", i).into(), DUMMY_SP).into(),
+                    ));
+                }
             }
             for p in a.params.iter() {
                 new_params.push(p.clone());
@@ -269,6 +300,7 @@ impl<C: Comments> ServerActions<C> {
                             name: action_ident.into(),
                             init: Some(Box::new(Expr::Arrow(ArrowExpr {
                                 params: new_params,
+                                body: action_body,
                                 ..a.clone()
                             }))),
                             definite: Default::default(),
@@ -312,11 +344,7 @@ impl<C: Comments> ServerActions<C> {
                     &mut self.annotations
                 },
                 ident.clone(),
-                ids_from_closure
-                    .iter()
-                    .cloned()
-                    .map(|id| Some(id.as_arg()))
-                    .collect(),
+                bound_args(&ids_from_closure, &action_id, encrypt_bound_args),
                 self.file_name.to_string(),
                 export_name.to_string(),
                 Some(action_ident.clone()),
@@ -326,6 +354,13 @@ impl<C: Comments> ServerActions<C> {
                 used_ids: &ids_from_closure,
             });
 
+            if encrypt_bound_args {
+                if let Some(body) = &mut f.body {
+                    body.stmts
+                        .insert(0, decrypt_bound_args(&action_id, ids_from_closure.len()));
+                }
+            }
+
             let new_fn = Function {
                 params: vec![
                     // ...args
@@ -359,14 +394,22 @@ impl<C: Comments> ServerActions<C> {
             let mut new_params: Vec<Param> = vec![];
 
             // add params from closure collected ids
-            for i in 0..ids_from_closure.len() {
+            if encrypt_bound_args {
                 new_params.push(Param {
                     span: DUMMY_SP,
                     decorators: vec![],
-                    pat: Pat::Ident(
-                        Ident::new(format!("$$ACTION_ARG_{}", i).into(), DUMMY_SP).into(),
-                    ),
+                    pat: Pat::Ident(Ident::new("$$ACTION_CLOSURE_BOUND".into(), DUMMY_SP).into()),
                 });
+            } else {
+                for i in 0..ids_from_closure.len() {
+                    new_params.push(Param {
+                        span: DUMMY_SP,
+                        decorators: vec![],
+                        pat: Pat::Ident(
+                            Ident::new(format!("$$ACTION_ARG_{}", i).into(), DUMMY_SP).into(),
+                        ),
+                    });
+                }
             }
             for p in f.params.iter() {
                 new_params.push(p.clone());
@@ -1069,8 +1112,36 @@ impl<C: Comments> VisitMut for ServerActions<C> {
                 type_only: false,
                 asserts: None,
             })));
-            // Make it the first item
-            new.rotate_right(1);
+
+            // import { encryptActionBoundArgs, decryptActionBoundArgs } from
+            // 'private-next-rsc-action-encryption'
+            let mut prepended = 1;
+            if self.has_encrypted_bound_args {
+                new.push(ModuleItem::ModuleDecl(ModuleDecl::Import(ImportDecl {
+                    span: DUMMY_SP,
+                    specifiers: ["encryptActionBoundArgs", "decryptActionBoundArgs"]
+                        .into_iter()
+                        .map(|name| {
+                            ImportSpecifier::Named(ImportNamedSpecifier {
+                                span: DUMMY_SP,
+                                local: quote_ident!(name),
+                                imported: None,
+                                is_type_only: false,
+                            })
+                        })
+                        .collect(),
+                    src: Box::new(Str {
+                        span: DUMMY_SP,
+                        value: "private-next-rsc-action-encryption".into(),
+                        raw: None,
+                    }),
+                    type_only: false,
+                    asserts: None,
+                })));
+                prepended += 1;
+            }
+            // Make them the first items
+            new.rotate_right(prepended);
         }
 
         *stmts = new;
@@ -1165,6 +1236,78 @@ fn generate_action_id(file_name: String, export_name: String) -> String {
     hex_encode(result)
 }
 
+fn bound_args(
+    ids_from_closure: &[Name],
+    action_id: &str,
+    encrypt: bool,
+) -> Vec<Option<ExprOrSpread>> {
+    let bound: Vec<Option<ExprOrSpread>> = ids_from_closure
+        .iter()
+        .cloned()
+        .map(|id| Some(id.as_arg()))
+        .collect();
+
+    if !encrypt {
+        return bound;
+    }
+
+    // [encryptActionBoundArgs("action_id", [arg1, arg2, arg3])]
+    vec![Some(
+        CallExpr {
+            span: DUMMY_SP,
+            callee: quote_ident!("encryptActionBoundArgs").as_callee(),
+            args: vec![
+                Expr::from(action_id.to_string()).as_arg(),
+                ArrayLit {
+                    span: DUMMY_SP,
+                    elems: bound,
+                }
+                .as_arg(),
+            ],
+            type_args: Default::default(),
+        }
+        .as_arg(),
+    )]
+}
+
+// var [$$ACTION_ARG_0, $$ACTION_ARG_1] = await decryptActionBoundArgs(
+//   "action_id", $$ACTION_CLOSURE_BOUND);
+fn decrypt_bound_args(action_id: &str, len: usize) -> Stmt {
+    Stmt::Decl(Decl::Var(Box::new(VarDecl {
+        span: DUMMY_SP,
+        kind: VarDeclKind::Var,
+        declare: false,
+        decls: vec![VarDeclarator {
+            span: DUMMY_SP,
+            name: Pat::Array(ArrayPat {
+                span: DUMMY_SP,
+                elems: (0..len)
+                    .map(|i| {
+                        Some(Pat::Ident(
+                            Ident::new(format!("$$ACTION_ARG_{}", i).into(), DUMMY_SP).into(),
+                        ))
+                    })
+                    .collect(),
+                optional: false,
+                type_ann: None,
+            }),
+            init: Some(Box::new(Expr::Await(AwaitExpr {
+                span: DUMMY_SP,
+                arg: Box::new(Expr::Call(CallExpr {
+                    span: DUMMY_SP,
+                    callee: quote_ident!("decryptActionBoundArgs").as_callee(),
+                    args: vec![
+                        Expr::from(action_id.to_string()).as_arg(),
+                        quote_ident!("$$ACTION_CLOSURE_BOUND").as_arg(),
+                    ],
+                    type_args: Default::default(),
+                })),
+            }))),
+            definite: false,
+        }],
+    })))
+}
+
 fn annotate_ident_as_action(
     annotations: &mut Vec<Stmt>,
     ident: Ident,
@@ -91,7 +91,10 @@ fn react_server_components_server_graph_errors(input: PathBuf) {
             server_components(
                 FileName::Real(PathBuf::from("/some-project/src/layout.js")),
                 next_swc::react_server_components::Config::WithOptions(
-                    next_swc::react_server_components::Options { is_server: true },
+                    next_swc::react_server_components::Options {
+                        is_server: true,
+                        proxy_import_source: None,
+                    },
                 ),
                 tr.comments.as_ref().clone(),
                 None,
@@ -115,7 +118,10 @@ fn react_server_components_client_graph_errors(input: PathBuf) {
             server_components(
                 FileName::Real(PathBuf::from("/some-project/src/page.js")),
                 next_swc::react_server_components::Config::WithOptions(
-                    next_swc::react_server_components::Options { is_server: false },
+                    next_swc::react_server_components::Options {
+                        is_server: false,
+                        proxy_import_source: None,
+                    },
                 ),
                 tr.comments.as_ref().clone(),
                 None,
@@ -161,7 +167,10 @@ fn react_server_actions_server_errors(input: PathBuf) {
                 server_components(
                     FileName::Real(PathBuf::from("/app/item.js")),
                     next_swc::react_server_components::Config::WithOptions(
-                        next_swc::react_server_components::Options { is_server: true },
+                        next_swc::react_server_components::Options {
+                            is_server: true,
+                            proxy_import_source: None,
+                        },
                     ),
                     tr.comments.as_ref().clone(),
                     None,
@@ -170,7 +179,8 @@ fn react_server_actions_server_errors(input: PathBuf) {
                     &FileName::Real("/app/item.js".into()),
                     server_actions::Config {
                         is_server: true,
-                        enabled: true
+                        enabled: true,
+                        encryption: false,
                     },
                     tr.comments.as_ref().clone(),
                 )
@@ -196,7 +206,10 @@ fn react_server_actions_client_errors(input: PathBuf) {
                 server_components(
                     FileName::Real(PathBuf::from("/app/item.js")),
                     next_swc::react_server_components::Config::WithOptions(
-                        next_swc::react_server_components::Options { is_server: false },
+                        next_swc::react_server_components::Options {
+                            is_server: false,
+                            proxy_import_source: None,
+                        },
                     ),
                     tr.comments.as_ref().clone(),
                     None,
@@ -205,7 +218,8 @@ fn react_server_actions_client_errors(input: PathBuf) {
                     &FileName::Real("/app/item.js".into()),
                     server_actions::Config {
                         is_server: false,
-                        enabled: true
+                        enabled: true,
+                        encryption: false,
                     },
                     tr.comments.as_ref().clone(),
                 )
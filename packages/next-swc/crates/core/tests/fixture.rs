@@ -265,7 +265,10 @@ fn react_server_components_server_graph_fixture(input: PathBuf) {
             server_components(
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 next_swc::react_server_components::Config::WithOptions(
-                    next_swc::react_server_components::Options { is_server: true },
+                    next_swc::react_server_components::Options {
+                        is_server: true,
+                        proxy_import_source: None,
+                    },
                 ),
                 tr.comments.as_ref().clone(),
                 None,
@@ -286,7 +289,10 @@ fn react_server_components_client_graph_fixture(input: PathBuf) {
             server_components(
                 FileName::Real(PathBuf::from("/some-project/src/some-file.js")),
                 next_swc::react_server_components::Config::WithOptions(
-                    next_swc::react_server_components::Options { is_server: false },
+                    next_swc::react_server_components::Options {
+                        is_server: false,
+                        proxy_import_source: None,
+                    },
                 ),
                 tr.comments.as_ref().clone(),
                 None,
@@ -327,7 +333,33 @@ fn server_actions_server_fixture(input: PathBuf) {
                     &FileName::Real("/app/item.js".into()),
                     server_actions::Config {
                         is_server: true,
-                        enabled: true
+                        enabled: true,
+                        encryption: false,
+                    },
+                    _tr.comments.as_ref().clone(),
+                )
+            )
+        },
+        &input,
+        &output,
+        Default::default(),
+    );
+}
+
+#[fixture("tests/fixture/server-actions/server-encryption/**/input.js")]
+fn server_actions_server_encryption_fixture(input: PathBuf) {
+    let output = input.parent().unwrap().join("output.js");
+    test_fixture(
+        syntax(),
+        &|_tr| {
+            chain!(
+                resolver(Mark::new(), Mark::new(), false),
+                server_actions(
+                    &FileName::Real("/app/item.js".into()),
+                    server_actions::Config {
+                        is_server: true,
+                        enabled: true,
+                        encryption: true,
                     },
                     _tr.comments.as_ref().clone(),
                 )
@@ -351,7 +383,8 @@ fn server_actions_client_fixture(input: PathBuf) {
                     &FileName::Real("/app/item.js".into()),
                     server_actions::Config {
                         is_server: false,
-                        enabled: true
+                        enabled: true,
+                        encryption: false,
                     },
                     _tr.comments.as_ref().clone(),
                 )
@@ -0,0 +1,8 @@
+'use client'
+
+import { useState } from 'react'
+
+export default function Counter() {
+  const [count] = useState(0)
+  return count
+}
@@ -0,0 +1,2 @@
+/* __next_internal_client_entry_do_not_use__  auto */ const { createProxy  } = require("private-next-rsc-mod-ref-proxy");
+module.exports = createProxy("/some-project/src/some-file.js");
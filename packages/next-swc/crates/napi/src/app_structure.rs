@@ -84,6 +84,8 @@ struct ComponentsForJs {
     template: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", rename = "not-found")]
     not_found: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", rename = "global-error")]
+    global_error: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     default: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -129,6 +131,7 @@ async fn prepare_components_for_js(
         loading,
         template,
         not_found,
+        global_error,
         default,
         route,
         metadata,
@@ -150,6 +153,7 @@ async fn prepare_components_for_js(
     add(&mut result.loading, project_path, loading).await?;
     add(&mut result.template, project_path, template).await?;
     add(&mut result.not_found, project_path, not_found).await?;
+    add(&mut result.global_error, project_path, global_error).await?;
     add(&mut result.default, project_path, default).await?;
     add(&mut result.route, project_path, route).await?;
     async fn add_meta<'a>(
@@ -63,6 +63,15 @@ impl TryFrom<NextBuildContext> for NextBuildOptions {
                     .context("NextBuildContext must provide rewrites")?
                     .into(),
             }),
+            routes: vec![],
+            custom_app: None,
+            custom_document: None,
+            dry_run: false,
+            precompression_level: None,
+            pages_manifest_hook: None,
+            node_output_dir: None,
+            client_output_dir: None,
+            prefix_manifest_chunk_paths: false,
         })
     }
 }
@@ -179,7 +188,8 @@ impl From<NapiRouteHas> for RouteHas {
 
 #[napi]
 pub async fn next_build(ctx: NextBuildContext) -> napi::Result<()> {
-    turbo_next_build(ctx.try_into()?).await.convert_err()
+    turbo_next_build(ctx.try_into()?).await.convert_err()?;
+    Ok(())
 }
 
 #[napi]
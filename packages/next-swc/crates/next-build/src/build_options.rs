@@ -1,7 +1,10 @@
-use std::path::PathBuf;
+use std::{fmt, path::PathBuf, sync::Arc};
 
+use anyhow::Result;
 use next_core::{next_config::Rewrites, turbopack::core::issue::IssueSeverity};
 
+use crate::manifests::PagesManifest;
+
 #[derive(Clone, Debug)]
 pub struct BuildOptions {
     /// The root directory of the workspace.
@@ -27,6 +30,61 @@ pub struct BuildOptions {
 
     /// The Next.js build context.
     pub build_context: Option<BuildContext>,
+
+    /// Only build the `pages` and `api` routes under these prefixes, like
+    /// `/admin/**`. All routes are built when this is empty.
+    pub routes: Vec<String>,
+
+    /// The `_app` page to use instead of the one in the pages directory, e.g. a
+    /// shared one from another workspace package. Relative to the workspace
+    /// root.
+    pub custom_app: Option<String>,
+
+    /// The `_document` page to use instead of the one in the pages directory.
+    /// Relative to the workspace root.
+    pub custom_document: Option<String>,
+
+    /// Compute the chunks of every page and return where they would be written
+    /// to, without emitting any asset or manifest.
+    pub dry_run: bool,
+
+    /// Write `.gz` and `.br` variants next to every emitted client JS and CSS
+    /// chunk, compressed at this level. Disabled when `None`.
+    pub precompression_level: Option<u32>,
+
+    /// Called with the pages manifest once every page has been added to it,
+    /// right before it is written, e.g. to add synthetic routes.
+    pub pages_manifest_hook: Option<PagesManifestHook>,
+
+    /// The directory the server output and manifests are written to, relative
+    /// to the project directory. Defaults to `.next`.
+    pub node_output_dir: Option<String>,
+
+    /// The directory the client output is written to, relative to the project
+    /// directory. Defaults to `.next`.
+    pub client_output_dir: Option<String>,
+
+    /// Write the client chunk paths of the build and client reference manifests
+    /// as URLs under the configured `assetPrefix` instead of relative to the
+    /// output directory.
+    pub prefix_manifest_chunk_paths: bool,
+}
+
+/// A callback that can modify the pages manifest before it is written. See
+/// [`PagesManifest`] for the entries it holds.
+#[derive(Clone)]
+pub struct PagesManifestHook(pub Arc<dyn Fn(&mut PagesManifest) -> Result<()> + Send + Sync>);
+
+impl PagesManifestHook {
+    pub fn new(hook: impl Fn(&mut PagesManifest) -> Result<()> + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for PagesManifestHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("PagesManifestHook")
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -0,0 +1,163 @@
+use std::{collections::HashMap, future::Future, pin::Pin};
+
+use anyhow::Result;
+use turbo_tasks::{primitives::StringReadRef, TryJoinIterExt, ValueToString};
+use turbopack_binding::turbopack::core::asset::{Asset, AssetVc};
+
+/// Writes a single asset of the given kind to the output directory.
+pub(crate) type ChunkSink = Box<
+    dyn Fn(AssetVc, ChunkKind) -> Pin<Box<dyn Future<Output = Result<()>> + Send>> + Send + Sync,
+>;
+
+/// What an emitted asset contains, so that it can be served and compressed
+/// without looking at its extension again.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ChunkKind {
+    Js,
+    Css,
+    SourceMap,
+    Wasm,
+    Font,
+    Other,
+}
+
+impl ChunkKind {
+    pub fn from_path(path: &str) -> Self {
+        if path.ends_with(".map") {
+            return ChunkKind::SourceMap;
+        }
+        match path.rsplit_once('.').map(|(_, extension)| extension) {
+            Some("js" | "mjs" | "cjs") => ChunkKind::Js,
+            Some("css") => ChunkKind::Css,
+            Some("wasm") => ChunkKind::Wasm,
+            Some("woff" | "woff2" | "eot" | "ttf" | "otf") => ChunkKind::Font,
+            _ => ChunkKind::Other,
+        }
+    }
+
+    /// The media type the asset should be served with, if it is known.
+    pub fn content_type(self) -> Option<&'static str> {
+        match self {
+            ChunkKind::Js => Some("application/javascript"),
+            ChunkKind::Css => Some("text/css"),
+            ChunkKind::SourceMap => Some("application/json"),
+            ChunkKind::Wasm => Some("application/wasm"),
+            ChunkKind::Font | ChunkKind::Other => None,
+        }
+    }
+
+    /// Whether the asset gets precompressed siblings. Only text-based chunks
+    /// benefit from it.
+    pub fn should_precompress(self) -> bool {
+        matches!(self, ChunkKind::Js | ChunkKind::Css)
+    }
+}
+
+/// Collects the assets a build emits, deduplicated by output path.
+///
+/// Pages and middleware can reach the same chunks, so they all add their
+/// assets to a single collector and every path is only written once.
+///
+/// A collector created with [`ChunkCollector::streaming`] hands every new
+/// asset to its sink as soon as it's added and only remembers its path, so
+/// that the whole chunk set never has to be held at once. Otherwise, only the
+/// paths are recorded. Every path is tagged with its [`ChunkKind`].
+#[derive(Default)]
+pub(crate) struct ChunkCollector {
+    paths: HashMap<StringReadRef, ChunkKind>,
+    sink: Option<ChunkSink>,
+}
+
+impl ChunkCollector {
+    pub fn streaming(sink: ChunkSink) -> Self {
+        ChunkCollector {
+            sink: Some(sink),
+            ..Default::default()
+        }
+    }
+
+    // TODO(alexkirsz) Deduplication should not happen at this level, but
+    // right now we have chunks with the same path being generated
+    // from different entrypoints, and writing them multiple times causes
+    // an infinite invalidation loop.
+    pub async fn extend(&mut self, assets: Vec<AssetVc>) -> Result<()> {
+        let assets = assets
+            .into_iter()
+            .map(|asset| async move { Ok((asset.ident().path().to_string().await?, asset)) })
+            .try_join()
+            .await?;
+        let mut new_assets = vec![];
+        for (path, asset) in assets {
+            if !self.paths.contains_key(&path) {
+                let kind = ChunkKind::from_path(&path);
+                self.paths.insert(path, kind);
+                new_assets.push((asset, kind));
+            }
+        }
+        if let Some(sink) = &self.sink {
+            new_assets
+                .into_iter()
+                .map(|(asset, kind)| sink(asset, kind))
+                .try_join()
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns a list of the collected paths and their content types, for
+    /// debugging.
+    pub fn describe(&self) -> String {
+        let mut paths: Vec<_> = self
+            .paths
+            .iter()
+            .map(|(path, kind)| (path.as_str(), kind))
+            .collect();
+        paths.sort_by_key(|(path, _)| *path);
+        paths
+            .into_iter()
+            .map(|(path, kind)| match kind.content_type() {
+                Some(content_type) => format!("  - {path} ({content_type})"),
+                None => format!("  - {path}"),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunk_kinds() {
+        assert_eq!(ChunkKind::from_path("static/chunks/page.js"), ChunkKind::Js);
+        assert_eq!(
+            ChunkKind::from_path("static/chunks/page.css"),
+            ChunkKind::Css
+        );
+        assert_eq!(
+            ChunkKind::from_path("static/chunks/page.js.map"),
+            ChunkKind::SourceMap
+        );
+        assert_eq!(
+            ChunkKind::from_path("server/edge/wasm_abc.wasm"),
+            ChunkKind::Wasm
+        );
+        assert_eq!(
+            ChunkKind::from_path("static/media/font.woff2"),
+            ChunkKind::Font
+        );
+        assert_eq!(
+            ChunkKind::from_path("static/media/logo.png"),
+            ChunkKind::Other
+        );
+    }
+
+    #[test]
+    fn precompressed_kinds() {
+        assert!(ChunkKind::Js.should_precompress());
+        assert!(ChunkKind::Css.should_precompress());
+        assert!(!ChunkKind::SourceMap.should_precompress());
+        assert!(!ChunkKind::Font.should_precompress());
+    }
+}
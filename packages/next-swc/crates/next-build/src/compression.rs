@@ -0,0 +1,57 @@
+use std::io::Write;
+
+use anyhow::Result;
+use flate2::{write::GzEncoder, Compression};
+
+/// The highest compression level accepted by gzip. Brotli accepts up to 11.
+const MAX_GZIP_LEVEL: u32 = 9;
+const MAX_BROTLI_QUALITY: u32 = 11;
+
+/// Compresses `content` with gzip. `level` is clamped to the range gzip
+/// supports.
+pub(crate) fn gzip(content: &[u8], level: u32) -> Result<Vec<u8>> {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::new(level.min(MAX_GZIP_LEVEL)));
+    encoder.write_all(content)?;
+    Ok(encoder.finish()?)
+}
+
+/// Compresses `content` with brotli. `level` is clamped to the range brotli
+/// supports.
+pub(crate) fn brotli(content: &[u8], level: u32) -> Result<Vec<u8>> {
+    let mut compressed = Vec::new();
+    {
+        let mut encoder =
+            brotli::CompressorWriter::new(&mut compressed, 4096, level.min(MAX_BROTLI_QUALITY), 22);
+        encoder.write_all(content)?;
+    }
+    Ok(compressed)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use super::*;
+
+    const CONTENT: &[u8] = b"console.log('hello world'); console.log('hello world');";
+
+    #[test]
+    fn gzip_round_trip() {
+        let compressed = gzip(CONTENT, 6).unwrap();
+        let mut decompressed = Vec::new();
+        flate2::read::GzDecoder::new(&compressed[..])
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, CONTENT);
+    }
+
+    #[test]
+    fn brotli_round_trip() {
+        let compressed = brotli(CONTENT, 20).unwrap();
+        let mut decompressed = Vec::new();
+        brotli::Decompressor::new(&compressed[..], 4096)
+            .read_to_end(&mut decompressed)
+            .unwrap();
+        assert_eq!(decompressed, CONTENT);
+    }
+}
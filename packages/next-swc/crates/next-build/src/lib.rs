@@ -4,16 +4,25 @@ use turbopack_binding::turbo::{
 };
 
 pub mod build_options;
+pub(crate) mod chunk_collector;
+pub(crate) mod compression;
 pub mod manifests;
+pub(crate) mod next_app;
 pub(crate) mod next_build;
+pub(crate) mod next_middleware;
 pub(crate) mod next_pages;
 
 use anyhow::Result;
 use turbo_tasks::{StatsType, TurboTasksBackendApi};
 
-pub use self::build_options::BuildOptions;
+pub use self::{
+    build_options::{BuildOptions, PagesManifestHook},
+    next_build::BuildPlan,
+};
 
-pub async fn build(options: BuildOptions) -> Result<()> {
+/// Builds the project. Returns what would have been emitted when
+/// `options.dry_run` is set, and `None` otherwise.
+pub async fn build(options: BuildOptions) -> Result<Option<BuildPlan>> {
     #[cfg(feature = "tokio_console")]
     console_subscriber::init();
     register();
@@ -31,13 +40,13 @@ pub async fn build(options: BuildOptions) -> Result<()> {
     tt.set_stats_type(stats_type);
 
     run_once(tt, async move {
-        next_build::next_build(TransientInstance::new(options)).await?;
-
-        Ok(())
+        let plan = *next_build::next_build(TransientInstance::new(options)).await?;
+        Ok(match plan {
+            Some(plan) => Some(plan.await?.clone_value()),
+            None => None,
+        })
     })
-    .await?;
-
-    Ok(())
+    .await
 }
 
 fn setup_tracing() {
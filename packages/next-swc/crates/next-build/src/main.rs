@@ -47,6 +47,45 @@ pub struct BuildCliArgs {
     /// MB.
     #[clap(long)]
     pub memory_limit: Option<usize>,
+
+    /// Only build the routes under these prefixes, e.g. `/admin/**`.
+    /// If no route is provided, all routes will be built.
+    #[clap(long, value_delimiter = ',')]
+    pub routes: Vec<String>,
+
+    /// The `_app` page to use instead of the one in the pages directory,
+    /// relative to the workspace root.
+    #[clap(long)]
+    pub custom_app: Option<String>,
+
+    /// The `_document` page to use instead of the one in the pages directory,
+    /// relative to the workspace root.
+    #[clap(long)]
+    pub custom_document: Option<String>,
+
+    /// Print the chunks each page would produce without writing anything.
+    #[clap(long)]
+    pub dry_run: bool,
+
+    /// Also write gzip and brotli compressed copies of the client chunks, at
+    /// the given compression level.
+    #[clap(long)]
+    pub precompress: Option<u32>,
+
+    /// The directory to write the server output to, relative to the project
+    /// directory. Defaults to `.next`.
+    #[clap(long)]
+    pub node_output_dir: Option<String>,
+
+    /// The directory to write the client output to, relative to the project
+    /// directory. Defaults to `.next`.
+    #[clap(long)]
+    pub client_output_dir: Option<String>,
+
+    /// Write the client chunk paths of the build manifest as URLs under the
+    /// configured `assetPrefix`.
+    #[clap(long)]
+    pub prefix_manifest_chunk_paths: bool,
 }
 
 fn main() {
@@ -88,7 +127,7 @@ async fn main_inner() -> Result<()> {
         return Ok(());
     }
 
-    next_build::build(BuildOptions {
+    let plan = next_build::build(BuildOptions {
         dir: args.dir,
         root: args.root,
         memory_limit: args.memory_limit,
@@ -97,6 +136,21 @@ async fn main_inner() -> Result<()> {
         log_detail: args.log_detail,
         full_stats: args.full_stats,
         build_context: None,
+        routes: args.routes,
+        custom_app: args.custom_app,
+        custom_document: args.custom_document,
+        dry_run: args.dry_run,
+        precompression_level: args.precompress,
+        pages_manifest_hook: None,
+        node_output_dir: args.node_output_dir,
+        client_output_dir: args.client_output_dir,
+        prefix_manifest_chunk_paths: args.prefix_manifest_chunk_paths,
     })
-    .await
+    .await?;
+
+    if let Some(plan) = plan {
+        println!("{}", serde_json::to_string_pretty(&plan)?);
+    }
+
+    Ok(())
 }
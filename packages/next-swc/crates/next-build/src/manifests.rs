@@ -1,14 +1,24 @@
 //! Type definitions for the Next.js manifest formats.
 
-use std::collections::HashMap;
+use std::collections::{btree_map::Entry, BTreeMap, BTreeSet, HashMap, HashSet};
 
+use anyhow::{bail, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
 use next_core::next_config::Rewrites;
 use serde::Serialize;
+use sha2::{Digest, Sha256, Sha384, Sha512};
 
+/// Maps every server-rendered pathname, including the special `/_app`,
+/// `/_document` and `/_error` pages, to the path of its server chunk relative
+/// to `.next/server`. Pathnames don't include the `basePath`, the server strips
+/// it before looking pages up.
+///
+/// Pages are ordered by pathname so the serialized manifest doesn't depend on
+/// the order pages were found in.
 #[derive(Serialize, Default, Debug)]
 pub struct PagesManifest {
     #[serde(flatten)]
-    pub pages: HashMap<String, String>,
+    pub pages: BTreeMap<String, String>,
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -21,6 +31,142 @@ pub struct BuildManifest {
     pub root_main_files: Vec<String>,
     pub pages: HashMap<String, Vec<String>>,
     pub amp_first_pages: Vec<String>,
+    /// Subresource integrity hashes of the client chunks, by chunk path. Only
+    /// populated when `experimental.sri` is configured.
+    #[serde(skip_serializing_if = "HashMap::is_empty")]
+    pub integrity: HashMap<String, String>,
+}
+
+/// Returns the subresource integrity hash of `content`, like `sha384-<base64
+/// digest>`.
+pub fn subresource_integrity(algorithm: &str, content: &[u8]) -> Result<String> {
+    let digest = match algorithm {
+        "sha256" => Sha256::digest(content).to_vec(),
+        "sha384" => Sha384::digest(content).to_vec(),
+        "sha512" => Sha512::digest(content).to_vec(),
+        _ => bail!(
+            "unsupported subresource integrity algorithm \"{}\", expected one of \"sha256\", \
+             \"sha384\" or \"sha512\"",
+            algorithm
+        ),
+    };
+    Ok(format!("{algorithm}-{}", STANDARD.encode(digest)))
+}
+
+impl BuildManifest {
+    /// Compares the pages of this manifest against a `previous` one.
+    ///
+    /// All returned lists are sorted so that the diff is deterministic.
+    pub fn diff(&self, previous: &BuildManifest) -> BuildManifestDiff {
+        let mut diff = BuildManifestDiff::default();
+
+        for (pathname, chunks) in &self.pages {
+            let Some(previous_chunks) = previous.pages.get(pathname) else {
+                diff.added.push(pathname.clone());
+                continue;
+            };
+
+            let chunks_diff = PageChunksDiff::new(previous_chunks, chunks);
+            if !chunks_diff.is_empty() {
+                diff.changed.insert(pathname.clone(), chunks_diff);
+            }
+        }
+
+        diff.removed = previous
+            .pages
+            .keys()
+            .filter(|pathname| !self.pages.contains_key(*pathname))
+            .cloned()
+            .collect();
+
+        diff.added.sort();
+        diff.removed.sort();
+
+        diff
+    }
+
+    /// Turns the chunk paths of this manifest, which are relative to the
+    /// output directory, into URLs under `asset_prefix`, like
+    /// `https://cdn.example.com/_next/static/chunks/main.js`.
+    pub fn apply_asset_prefix(&mut self, asset_prefix: &str) {
+        let url = |path: &str| asset_url(asset_prefix, path);
+
+        for files in [
+            &mut self.dev_files,
+            &mut self.amp_dev_files,
+            &mut self.polyfill_files,
+            &mut self.low_priority_files,
+            &mut self.root_main_files,
+        ]
+        .into_iter()
+        .chain(self.pages.values_mut())
+        {
+            for file in files.iter_mut() {
+                *file = url(file);
+            }
+        }
+        self.integrity = self
+            .integrity
+            .drain()
+            .map(|(path, integrity)| (url(&path), integrity))
+            .collect();
+    }
+}
+
+/// Returns the URL of the chunk at `path`, relative to the client output
+/// directory, under `asset_prefix`.
+fn asset_url(asset_prefix: &str, path: &str) -> String {
+    format!("{}/_next/{path}", asset_prefix.trim_end_matches('/'))
+}
+
+/// The difference between the pages of two [BuildManifest]s.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct BuildManifestDiff {
+    /// Pathnames that are only present in the new manifest.
+    pub added: Vec<String>,
+    /// Pathnames that are only present in the previous manifest.
+    pub removed: Vec<String>,
+    /// Pathnames present in both manifests whose chunk paths differ.
+    pub changed: HashMap<String, PageChunksDiff>,
+}
+
+impl BuildManifestDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// The difference between the chunk paths of a single page.
+#[derive(Default, Debug, PartialEq, Eq)]
+pub struct PageChunksDiff {
+    /// Chunk paths that are only referenced by the new manifest.
+    pub added: Vec<String>,
+    /// Chunk paths that are only referenced by the previous manifest.
+    pub removed: Vec<String>,
+}
+
+impl PageChunksDiff {
+    fn new(previous: &[String], current: &[String]) -> Self {
+        let previous_set: HashSet<&String> = previous.iter().collect();
+        let current_set: HashSet<&String> = current.iter().collect();
+
+        let mut added: Vec<String> = current_set
+            .difference(&previous_set)
+            .map(|chunk| (*chunk).clone())
+            .collect();
+        let mut removed: Vec<String> = previous_set
+            .difference(&current_set)
+            .map(|chunk| (*chunk).clone())
+            .collect();
+        added.sort();
+        removed.sort();
+
+        Self { added, removed }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
 }
 
 #[derive(Serialize, Debug)]
@@ -39,26 +185,78 @@ impl Default for MiddlewaresManifest {
 }
 
 #[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
 pub struct MiddlewaresManifestV2 {
-    pub sorted_middleware: Vec<()>,
-    pub middleware: HashMap<String, ()>,
-    pub functions: HashMap<String, ()>,
+    pub sorted_middleware: Vec<String>,
+    pub middleware: HashMap<String, EdgeFunctionDefinition>,
+    pub functions: HashMap<String, EdgeFunctionDefinition>,
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct EdgeFunctionDefinition {
+    /// Paths of the chunks to evaluate, relative to the `.next` directory. The
+    /// entry chunk comes last.
+    pub files: Vec<String>,
+    pub name: String,
+    pub page: String,
+    pub matchers: Vec<MiddlewareMatcher>,
+    pub wasm: Vec<AssetBinding>,
+    pub assets: Vec<AssetBinding>,
+    /// The regions to deploy the function to, from the page's `config` export.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub regions: Option<Vec<String>>,
 }
 
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MiddlewareMatcher {
+    pub regexp: String,
+    pub original_source: String,
+}
+
+#[derive(Serialize, Default, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssetBinding {
+    pub name: String,
+    pub file_path: String,
+}
+
+/// Maps the chunk group id of each `next/dynamic` import to the client chunks
+/// it loads.
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ReactLoadableManifest {
     #[serde(flatten)]
-    pub manifest: HashMap<String, ReactLoadableManifestEntry>,
+    pub manifest: BTreeMap<String, ReactLoadableManifestEntry>,
 }
 
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ReactLoadableManifestEntry {
-    pub id: u32,
+    pub id: String,
     pub files: Vec<String>,
 }
 
+/// Maps each client chunk to the pages that load it, so that a changed chunk
+/// can be traced back to exactly the routes it affects, e.g. to purge them
+/// from a CDN.
+#[derive(Serialize, Default, Debug)]
+pub struct ChunkRoutesManifest {
+    #[serde(flatten)]
+    pub chunks: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl ChunkRoutesManifest {
+    /// Records that the page at `pathname` loads `chunk_path`.
+    pub fn insert(&mut self, chunk_path: &str, pathname: &str) {
+        self.chunks
+            .entry(chunk_path.to_string())
+            .or_default()
+            .insert(pathname.to_string());
+    }
+}
+
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct NextFontManifest {
@@ -107,14 +305,107 @@ pub enum ActionManifestWorkerEntry {
     Number(f64),
 }
 
+/// Client references are collected concurrently, so all maps are ordered by
+/// key to keep the serialized manifest identical across builds.
 #[derive(Serialize, Default, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct ClientReferenceManifest {
     pub client_modules: ManifestNode,
-    pub ssr_module_mapping: HashMap<String, ManifestNode>,
+    pub ssr_module_mapping: BTreeMap<String, ManifestNode>,
     #[serde(rename = "edgeSSRModuleMapping")]
-    pub edge_ssr_module_mapping: HashMap<String, ManifestNode>,
-    pub css_files: HashMap<String, Vec<String>>,
+    pub edge_ssr_module_mapping: BTreeMap<String, ManifestNode>,
+    pub css_files: BTreeMap<String, Vec<String>>,
+}
+
+impl ClientReferenceManifest {
+    /// Merges the manifest of another build shard into this one.
+    ///
+    /// A module present in both manifests must have identical entries, as
+    /// both describe the same module. The CSS files of an entry are unioned.
+    pub fn merge(&mut self, other: ClientReferenceManifest) -> Result<()> {
+        self.client_modules.merge(other.client_modules)?;
+        for (key, node) in other.ssr_module_mapping {
+            self.ssr_module_mapping
+                .entry(key)
+                .or_default()
+                .merge(node)?;
+        }
+        for (key, node) in other.edge_ssr_module_mapping {
+            self.edge_ssr_module_mapping
+                .entry(key)
+                .or_default()
+                .merge(node)?;
+        }
+        for (entry, files) in other.css_files {
+            let existing = self.css_files.entry(entry).or_default();
+            for file in files {
+                if !existing.contains(&file) {
+                    existing.push(file);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Turns the client chunk paths of this manifest into URLs under
+    /// `asset_prefix`, like [BuildManifest::apply_asset_prefix]. The SSR
+    /// module mappings point at server chunks and are left alone.
+    pub fn apply_asset_prefix(&mut self, asset_prefix: &str) {
+        let entries = self.client_modules.module_exports.values_mut();
+        for chunks in entries
+            .map(|entry| &mut entry.chunks)
+            .chain(self.css_files.values_mut())
+        {
+            for chunk in chunks.iter_mut() {
+                *chunk = asset_url(asset_prefix, chunk);
+            }
+        }
+    }
+}
+
+/// Returns the key of a client reference in [ManifestNode::module_exports].
+///
+/// The key is `{server_path}#{export_name}`, like the one the Next.js flight
+/// runtime uses. `%` and `#` are percent-encoded in both components so that the
+/// key can always be split back unambiguously. This leaves keys for regular
+/// paths and identifiers untouched.
+pub fn client_reference_module_key(server_path: &str, export_name: &str) -> String {
+    format!(
+        "{}#{}",
+        escape_module_key_component(server_path),
+        escape_module_key_component(export_name)
+    )
+}
+
+/// Splits a key created by [client_reference_module_key] back into its server
+/// path and export name.
+pub fn parse_client_reference_module_key(key: &str) -> Option<(String, String)> {
+    let (server_path, export_name) = key.split_once('#')?;
+    Some((
+        unescape_module_key_component(server_path)?,
+        unescape_module_key_component(export_name)?,
+    ))
+}
+
+fn escape_module_key_component(component: &str) -> String {
+    component.replace('%', "%25").replace('#', "%23")
+}
+
+fn unescape_module_key_component(component: &str) -> Option<String> {
+    let mut result = String::with_capacity(component.len());
+    let mut rest = component;
+    while let Some(index) = rest.find('%') {
+        result.push_str(&rest[..index]);
+        let escaped = rest.get(index..index + 3)?;
+        match escaped {
+            "%25" => result.push('%'),
+            "%23" => result.push('#'),
+            _ => return None,
+        }
+        rest = &rest[index + 3..];
+    }
+    result.push_str(rest);
+    Some(result)
 }
 
 #[derive(Serialize, Default, Debug)]
@@ -128,10 +419,31 @@ pub struct ClientCssReferenceManifest {
 #[serde(rename_all = "camelCase")]
 pub struct ManifestNode {
     #[serde(flatten)]
-    pub module_exports: HashMap<String, ManifestNodeEntry>,
+    pub module_exports: BTreeMap<String, ManifestNodeEntry>,
 }
 
-#[derive(Serialize, Debug)]
+impl ManifestNode {
+    fn merge(&mut self, other: ManifestNode) -> Result<()> {
+        for (key, entry) in other.module_exports {
+            match self.module_exports.entry(key) {
+                Entry::Vacant(vacant) => {
+                    vacant.insert(entry);
+                }
+                Entry::Occupied(occupied) => {
+                    if *occupied.get() != entry {
+                        bail!(
+                            "conflicting client reference manifest entries for {}",
+                            occupied.key()
+                        );
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct ManifestNodeEntry {
     pub id: ModuleId,
@@ -140,7 +452,7 @@ pub struct ManifestNodeEntry {
     pub r#async: bool,
 }
 
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 #[serde(untagged)]
 pub enum ModuleId {
@@ -165,6 +477,183 @@ pub struct AppBuildManifest {
     pub pages: HashMap<String, Vec<String>>,
 }
 
+/// Next.js' `routes-manifest.json`. Redirects and headers aren't supported by
+/// Turbopack's Next.js config yet, so they are always empty.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutesManifest<'a> {
+    pub version: u32,
+    pub pages404: bool,
+    pub base_path: String,
+    pub redirects: Vec<serde_json::Value>,
+    pub headers: Vec<serde_json::Value>,
+    pub rewrites: &'a Rewrites,
+    pub dynamic_routes: Vec<RouteRegex>,
+    pub static_routes: Vec<RouteRegex>,
+    /// The `/_next/data` routes of pages with `getStaticProps` or
+    /// `getServerSideProps`. The build doesn't know which pages have them, so
+    /// this is always empty.
+    pub data_routes: Vec<serde_json::Value>,
+    /// The status code each custom error page, `/404` or `/500`, is rendered
+    /// with, by pathname. This isn't part of the webpack build's manifest,
+    /// it lets deployment tooling tell error pages apart from regular ones.
+    pub error_pages: &'a BTreeMap<String, u16>,
+}
+
+impl<'a> RoutesManifest<'a> {
+    /// Builds the manifest from `sorted_pages`, the pathnames of both pages and
+    /// app routes, which must already be in the order routes should be matched
+    /// in.
+    pub fn new(
+        base_path: String,
+        rewrites: &'a Rewrites,
+        sorted_pages: &[String],
+        error_pages: &'a BTreeMap<String, u16>,
+    ) -> Self {
+        RoutesManifest {
+            version: 3,
+            pages404: sorted_pages.iter().any(|page| page == "/404"),
+            base_path,
+            redirects: vec![],
+            headers: vec![],
+            rewrites,
+            dynamic_routes: sorted_pages
+                .iter()
+                .filter(|page| is_dynamic_route(page))
+                .map(|page| RouteRegex::new(page))
+                .collect(),
+            static_routes: sorted_pages
+                .iter()
+                .filter(|page| !is_dynamic_route(page) && !is_reserved_page(page))
+                .map(|page| RouteRegex::new(page))
+                .collect(),
+            data_routes: vec![],
+            error_pages,
+        }
+    }
+}
+
+/// Whether `page` has a dynamic segment, like `[id]` or `[...slug]`.
+fn is_dynamic_route(page: &str) -> bool {
+    page.split('/')
+        .any(|segment| segment.len() > 2 && segment.starts_with('[') && segment.ends_with(']'))
+}
+
+/// Whether `page` is one of the special pages or an API route, which aren't
+/// listed as static routes. Mirrors `isReservedPage`.
+fn is_reserved_page(page: &str) -> bool {
+    ["/_app", "/_error", "/_document", "/api"]
+        .iter()
+        .any(|reserved| match page.strip_prefix(reserved) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        })
+}
+
+#[derive(Serialize, Debug, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteRegex {
+    pub page: String,
+    pub regex: String,
+    pub route_keys: BTreeMap<String, String>,
+    pub named_regex: String,
+}
+
+impl RouteRegex {
+    /// Returns the regexes matching `page`. Mirrors `getNamedRouteRegex` with
+    /// prefixed route keys.
+    pub fn new(page: &str) -> Self {
+        let page_without_slash = page.strip_suffix('/').unwrap_or(page);
+        let mut regex = String::new();
+        let mut named_regex = String::new();
+        let mut route_keys = BTreeMap::new();
+
+        for segment in page_without_slash.split('/').skip(1) {
+            let Some(param) = segment
+                .strip_prefix('[')
+                .and_then(|param| param.strip_suffix(']'))
+                .filter(|param| !param.is_empty())
+            else {
+                let escaped = escape_route_regex(segment);
+                regex.push('/');
+                regex.push_str(&escaped);
+                named_regex.push('/');
+                named_regex.push_str(&escaped);
+                continue;
+            };
+            let (optional, param) = match param
+                .strip_prefix('[')
+                .and_then(|param| param.strip_suffix(']'))
+            {
+                Some(param) => (true, param),
+                None => (false, param),
+            };
+            let (repeat, key) = match param.strip_prefix("...") {
+                Some(key) => (true, key),
+                None => (false, param),
+            };
+
+            let mut cleaned_key = format!(
+                "nxtP{}",
+                key.chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '_')
+                    .collect::<String>()
+            );
+            if cleaned_key.len() > 30 {
+                cleaned_key = safe_route_key(route_keys.len());
+            }
+            route_keys.insert(cleaned_key.clone(), format!("nxtP{key}"));
+
+            let (pattern, named_pattern) = match (repeat, optional) {
+                (true, true) => (
+                    "(?:/(.+?))?".to_string(),
+                    format!("(?:/(?<{cleaned_key}>.+?))?"),
+                ),
+                (true, false) => ("/(.+?)".to_string(), format!("/(?<{cleaned_key}>.+?)")),
+                (false, _) => (
+                    "/([^/]+?)".to_string(),
+                    format!("/(?<{cleaned_key}>[^/]+?)"),
+                ),
+            };
+            regex.push_str(&pattern);
+            named_regex.push_str(&named_pattern);
+        }
+
+        RouteRegex {
+            page: page.to_string(),
+            regex: format!("^{regex}(?:/)?$"),
+            route_keys,
+            named_regex: format!("^{named_regex}(?:/)?$"),
+        }
+    }
+}
+
+/// Escapes the characters that are special in a JavaScript regex.
+fn escape_route_regex(segment: &str) -> String {
+    let mut escaped = String::with_capacity(segment.len());
+    for c in segment.chars() {
+        if "|\\{}()[]^$+*?.-".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Returns a short, unique key for a route param whose own name can't be used
+/// as a regex group name.
+fn safe_route_key(index: usize) -> String {
+    let mut key = String::new();
+    let mut index = index;
+    loop {
+        key.insert(0, (b'a' + (index % 26) as u8) as char);
+        if index < 26 {
+            break key;
+        }
+        index = index / 26 - 1;
+    }
+}
+
 // TODO(alexkirsz) Unify with the one for dev.
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
@@ -177,3 +666,312 @@ pub struct ClientBuildManifest<'a> {
     #[serde(flatten)]
     pub pages: HashMap<String, Vec<&'a str>>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest(pages: &[(&str, &[&str])]) -> BuildManifest {
+        BuildManifest {
+            pages: pages
+                .iter()
+                .map(|(pathname, chunks)| {
+                    (
+                        pathname.to_string(),
+                        chunks.iter().map(|chunk| chunk.to_string()).collect(),
+                    )
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pages_manifest_order() {
+        let mut pages_manifest = PagesManifest::default();
+        pages_manifest
+            .pages
+            .insert("/blog".to_string(), "pages/blog.js".to_string());
+        pages_manifest
+            .pages
+            .insert("/_app".to_string(), "pages/_app.js".to_string());
+
+        assert_eq!(
+            serde_json::to_string(&pages_manifest).unwrap(),
+            r#"{"/_app":"pages/_app.js","/blog":"pages/blog.js"}"#
+        );
+    }
+
+    #[test]
+    fn dynamic_routes() {
+        let route = RouteRegex::new("/blog/about");
+        assert_eq!(route.regex, "^/blog/about(?:/)?$");
+        assert!(route.route_keys.is_empty());
+        assert_eq!(RouteRegex::new("/").regex, "^(?:/)?$");
+
+        let route = RouteRegex::new("/blog/[slug]");
+        assert_eq!(route.regex, "^/blog/([^/]+?)(?:/)?$");
+        assert_eq!(route.named_regex, "^/blog/(?<nxtPslug>[^/]+?)(?:/)?$");
+        assert_eq!(
+            route.route_keys,
+            BTreeMap::from([("nxtPslug".to_string(), "nxtPslug".to_string())])
+        );
+
+        let route = RouteRegex::new("/docs.v2/[...path]");
+        assert_eq!(route.regex, "^/docs\\.v2/(.+?)(?:/)?$");
+        assert_eq!(route.named_regex, "^/docs\\.v2/(?<nxtPpath>.+?)(?:/)?$");
+
+        let route = RouteRegex::new("/shop/[[...filters]]");
+        assert_eq!(route.regex, "^/shop(?:/(.+?))?(?:/)?$");
+        assert_eq!(route.named_regex, "^/shop(?:/(?<nxtPfilters>.+?))?(?:/)?$");
+    }
+
+    #[test]
+    fn routes_manifest() {
+        let rewrites = Rewrites::default();
+        let sorted_pages = [
+            "/",
+            "/_app",
+            "/404",
+            "/about",
+            "/api/hello",
+            "/api/users/[id]",
+            "/blog/[slug]",
+        ]
+        .map(str::to_string);
+        let error_pages = BTreeMap::from([("/404".to_string(), 404)]);
+        let manifest = RoutesManifest::new(String::new(), &rewrites, &sorted_pages, &error_pages);
+
+        let pages = |routes: &[RouteRegex]| -> Vec<String> {
+            routes.iter().map(|route| route.page.clone()).collect()
+        };
+        assert_eq!(
+            pages(&manifest.dynamic_routes),
+            ["/api/users/[id]", "/blog/[slug]"]
+        );
+        assert_eq!(pages(&manifest.static_routes), ["/", "/404", "/about"]);
+
+        let json = serde_json::to_value(&manifest).unwrap();
+        for key in ["redirects", "headers", "dataRoutes"] {
+            assert_eq!(json[key], serde_json::json!([]));
+        }
+        assert_eq!(json["rewrites"]["beforeFiles"], serde_json::json!([]));
+        assert_eq!(json["pages404"], serde_json::json!(true));
+        assert_eq!(json["errorPages"], serde_json::json!({ "/404": 404 }));
+    }
+
+    #[test]
+    fn asset_prefix() {
+        let mut build_manifest = manifest(&[("/", &["static/chunks/pages/index.js"])]);
+        build_manifest
+            .root_main_files
+            .push("static/chunks/main.js".to_string());
+        build_manifest.integrity.insert(
+            "static/chunks/main.js".to_string(),
+            "sha256-abc".to_string(),
+        );
+
+        build_manifest.apply_asset_prefix("https://cdn.example.com/");
+
+        assert_eq!(
+            build_manifest.pages["/"],
+            vec!["https://cdn.example.com/_next/static/chunks/pages/index.js"]
+        );
+        assert_eq!(
+            build_manifest.root_main_files,
+            vec!["https://cdn.example.com/_next/static/chunks/main.js"]
+        );
+        assert_eq!(
+            build_manifest.integrity["https://cdn.example.com/_next/static/chunks/main.js"],
+            "sha256-abc"
+        );
+    }
+
+    #[test]
+    fn client_reference_asset_prefix() {
+        let entry = |chunks: &[&str]| ManifestNodeEntry {
+            id: ModuleId::Number(1.0),
+            name: "*".to_string(),
+            chunks: chunks.iter().map(|chunk| chunk.to_string()).collect(),
+            r#async: false,
+        };
+        let mut manifest = ClientReferenceManifest::default();
+        manifest.client_modules.module_exports.insert(
+            "app/client.tsx#".to_string(),
+            entry(&["static/chunks/client.js"]),
+        );
+        manifest.ssr_module_mapping.insert(
+            "1".to_string(),
+            ManifestNode {
+                module_exports: [("*".to_string(), entry(&["server/app/client.js"]))].into(),
+            },
+        );
+        manifest.css_files.insert(
+            "app/page".to_string(),
+            vec!["static/css/page.css".to_string()],
+        );
+
+        manifest.apply_asset_prefix("https://cdn.example.com");
+
+        assert_eq!(
+            manifest.client_modules.module_exports["app/client.tsx#"].chunks,
+            vec!["https://cdn.example.com/_next/static/chunks/client.js"]
+        );
+        assert_eq!(
+            manifest.ssr_module_mapping["1"].module_exports["*"].chunks,
+            vec!["server/app/client.js"]
+        );
+        assert_eq!(
+            manifest.css_files["app/page"],
+            vec!["https://cdn.example.com/_next/static/css/page.css"]
+        );
+    }
+
+    #[test]
+    fn diff_pages() {
+        let previous = manifest(&[
+            ("/", &["a.js", "b.js"]),
+            ("/about", &["a.js"]),
+            ("/old", &["c.js"]),
+        ]);
+        let current = manifest(&[
+            ("/", &["a.js", "d.js"]),
+            ("/about", &["a.js"]),
+            ("/new", &["e.js"]),
+        ]);
+
+        let diff = current.diff(&previous);
+
+        assert_eq!(diff.added, vec!["/new"]);
+        assert_eq!(diff.removed, vec!["/old"]);
+        assert_eq!(diff.changed.len(), 1);
+        assert_eq!(
+            diff.changed["/"],
+            PageChunksDiff {
+                added: vec!["d.js".to_string()],
+                removed: vec!["b.js".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn diff_identical() {
+        let manifest = manifest(&[("/", &["a.js"])]);
+        assert!(manifest.diff(&manifest).is_empty());
+    }
+
+    #[test]
+    fn module_key_regular_identifier() {
+        let key = client_reference_module_key("[project]/app/button.tsx", "Button");
+        assert_eq!(key, "[project]/app/button.tsx#Button");
+        assert_eq!(
+            parse_client_reference_module_key(&key),
+            Some(("[project]/app/button.tsx".to_string(), "Button".to_string()))
+        );
+    }
+
+    #[test]
+    fn module_key_round_trip() {
+        for (server_path, export_name) in [
+            ("[project]/app/a#b.tsx", "weird#name"),
+            ("[project]/app/100%.tsx", "%23"),
+            ("[project]/app/page.tsx", ""),
+        ] {
+            let key = client_reference_module_key(server_path, export_name);
+            assert_eq!(key.matches('#').count(), 1);
+            assert_eq!(
+                parse_client_reference_module_key(&key),
+                Some((server_path.to_string(), export_name.to_string()))
+            );
+        }
+    }
+
+    #[test]
+    fn module_key_invalid() {
+        assert_eq!(parse_client_reference_module_key("no-separator"), None);
+        assert_eq!(parse_client_reference_module_key("path#%2"), None);
+        assert_eq!(parse_client_reference_module_key("path#%41"), None);
+    }
+
+    #[test]
+    fn client_reference_manifest_order() {
+        let entry = |name: &str| ManifestNodeEntry {
+            id: ModuleId::String(name.to_string()),
+            name: name.to_string(),
+            chunks: vec![],
+            r#async: false,
+        };
+        let serialize = |names: &[&str]| {
+            let mut manifest = ClientReferenceManifest::default();
+            for name in names {
+                manifest
+                    .client_modules
+                    .module_exports
+                    .insert(name.to_string(), entry(name));
+            }
+            serde_json::to_string(&manifest).unwrap()
+        };
+
+        assert_eq!(serialize(&["a", "b", "c"]), serialize(&["c", "a", "b"]));
+    }
+
+    #[test]
+    fn merge_client_reference_manifests() {
+        let entry = |id: &str| ManifestNodeEntry {
+            id: ModuleId::String(id.to_string()),
+            name: "*".to_string(),
+            chunks: vec![],
+            r#async: false,
+        };
+        let shard = |id: &str, css: &[&str]| {
+            let mut manifest = ClientReferenceManifest::default();
+            manifest
+                .client_modules
+                .module_exports
+                .insert(format!("{id}#*"), entry(id));
+            manifest.css_files.insert(
+                "app/page".to_string(),
+                css.iter().map(|file| file.to_string()).collect(),
+            );
+            manifest
+        };
+
+        let mut manifest = shard("a", &["shared.css", "a.css"]);
+        manifest
+            .merge(shard("b", &["shared.css", "b.css"]))
+            .unwrap();
+        assert_eq!(
+            manifest
+                .client_modules
+                .module_exports
+                .keys()
+                .collect::<Vec<_>>(),
+            vec!["a#*", "b#*"]
+        );
+        assert_eq!(
+            manifest.css_files["app/page"],
+            vec!["shared.css", "a.css", "b.css"]
+        );
+
+        manifest.merge(shard("a", &[])).unwrap();
+        let mut conflicting = shard("a", &[]);
+        conflicting
+            .client_modules
+            .module_exports
+            .insert("b#*".to_string(), entry("other"));
+        assert!(manifest.merge(conflicting).is_err());
+    }
+
+    #[test]
+    fn integrity() {
+        assert_eq!(
+            subresource_integrity("sha384", b"").unwrap(),
+            "sha384-OLBgp1GsljhM2TJ+sbHjaiH9txEUvgdDTAzHv2P24donTt6/529l+9Ua0vFImLlb"
+        );
+        assert_eq!(
+            subresource_integrity("sha256", b"alert(1)").unwrap(),
+            "sha256-bhHHL3z2vDgxUt0W3dWQOrprscmda2Y5pLsLg4GF+pI="
+        );
+        assert!(subresource_integrity("md5", b"").is_err());
+    }
+}
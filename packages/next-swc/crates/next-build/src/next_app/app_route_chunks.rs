@@ -0,0 +1,210 @@
+use anyhow::{bail, Result};
+use next_core::{
+    app_segment_config::parse_segment_config_from_source,
+    app_structure::{find_app_dir_if_enabled, get_entrypoints, Entrypoint},
+    mode::NextMode,
+    next_config::NextConfigVc,
+    next_edge::{
+        context::{
+            get_edge_compile_time_info, get_edge_module_options_context,
+            get_edge_resolve_options_context, should_minify_edge_chunks,
+        },
+        entry::edge_app_route_entry,
+    },
+    next_server::{
+        app_route_entry, get_server_compile_time_info, get_server_module_options_context,
+        get_server_resolve_options_context, ServerContextType,
+    },
+    pathname_for_path, NextRuntime, PathType,
+};
+use turbo_tasks::primitives::{BoolVc, StringVc};
+use turbopack_binding::{
+    turbo::{tasks::Value, tasks_env::ProcessEnvVc, tasks_fs::FileSystemPathVc},
+    turbopack::{
+        core::{
+            asset::AssetVc,
+            environment::ServerAddrVc,
+            reference_type::{EntryReferenceSubType, ReferenceType},
+            source_asset::SourceAssetVc,
+        },
+        node::execution_context::ExecutionContextVc,
+        turbopack::{transition::TransitionsByNameVc, ModuleAssetContextVc},
+    },
+};
+
+use crate::next_pages::{
+    node_context::PagesBuildNodeContextVc, page_chunks::get_node_runtime_entries,
+};
+
+#[turbo_tasks::value(transparent)]
+pub struct AppRouteChunks(Vec<AppRouteChunkVc>);
+
+/// The server chunk of an app route handler (`app/**/route.ts`). Route
+/// handlers are only ever executed on the server, so unlike pages they have
+/// no client chunks.
+#[turbo_tasks::value]
+pub struct AppRouteChunk {
+    /// The pathname the route handler serves. Route groups don't appear in
+    /// it.
+    pub pathname: StringVc,
+    /// The path of the route handler module within the app directory, without
+    /// its extension, like `api/hello/route`.
+    pub original_path: StringVc,
+    /// The runtime from the route handler's `runtime` segment config.
+    pub runtime: NextRuntime,
+    /// The server side chunk. For edge route handlers, this is built for the
+    /// edge runtime.
+    pub node_chunk: AssetVc,
+}
+
+/// Returns the chunks of every route handler in the app directory, or none if
+/// the app directory is disabled or missing.
+#[turbo_tasks::function]
+pub async fn get_app_route_chunks(
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    node_root: FileSystemPathVc,
+    env: ProcessEnvVc,
+    next_config: NextConfigVc,
+    node_addr: ServerAddrVc,
+) -> Result<AppRouteChunksVc> {
+    let Some(app_dir) = *find_app_dir_if_enabled(project_root, next_config).await? else {
+        return Ok(AppRouteChunksVc::cell(vec![]));
+    };
+
+    let mode = NextMode::Build;
+    let node_ty = Value::new(ServerContextType::AppRoute { app_dir });
+
+    let node_compile_time_info = get_server_compile_time_info(mode, env, node_addr);
+    let node_resolve_options_context = get_server_resolve_options_context(
+        project_root,
+        node_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
+    let node_module_options_context = get_server_module_options_context(
+        project_root,
+        execution_context,
+        node_ty,
+        mode,
+        next_config,
+    );
+    let node_asset_context = ModuleAssetContextVc::new(
+        TransitionsByNameVc::cell(Default::default()),
+        node_compile_time_info,
+        node_module_options_context,
+        node_resolve_options_context,
+    )
+    .into();
+
+    let node_runtime_entries = get_node_runtime_entries(project_root, env, next_config);
+
+    let node_build_context = PagesBuildNodeContextVc::new(
+        project_root,
+        node_root,
+        node_asset_context,
+        node_runtime_entries,
+        BoolVc::cell(false),
+    );
+
+    // Route handlers exporting `runtime = 'edge'` are built for the edge worker
+    // environment instead. There is no server to talk to in a build.
+    let edge_compile_time_info = get_edge_compile_time_info(mode, project_root, None, None);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_root,
+        node_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
+    let edge_module_options_context = get_edge_module_options_context(
+        project_root,
+        execution_context,
+        node_ty,
+        mode,
+        next_config,
+    );
+    let edge_asset_context = ModuleAssetContextVc::new(
+        TransitionsByNameVc::cell(Default::default()),
+        edge_compile_time_info,
+        edge_module_options_context,
+        edge_resolve_options_context,
+    )
+    .into();
+
+    let edge_build_context = PagesBuildNodeContextVc::new(
+        project_root,
+        node_root,
+        edge_asset_context,
+        node_runtime_entries,
+        should_minify_edge_chunks(mode, next_config),
+    );
+
+    let entrypoints = get_entrypoints(app_dir, next_config.page_extensions()).await?;
+    let app_dir_value = app_dir.await?;
+    let mut chunks = vec![];
+    for entrypoint in entrypoints.values() {
+        let Entrypoint::AppRoute { path } = *entrypoint else {
+            continue;
+        };
+        let Some(original_path) = app_dir_value.get_path_to(&*path.await?) else {
+            bail!("route handler {} is not in the app directory", path.await?);
+        };
+        let original_path = original_path
+            .rsplit_once('.')
+            .map_or(original_path, |(stem, _)| stem)
+            .to_string();
+
+        let source_asset = SourceAssetVc::new(path).into();
+        let reference_type = Value::new(ReferenceType::Entry(EntryReferenceSubType::AppRoute));
+        let chunk_path = format!("server/app/{original_path}.js");
+
+        let node_module = node_build_context.node_module(source_asset, reference_type.clone());
+        let runtime = parse_segment_config_from_source(node_module)
+            .await?
+            .runtime
+            .unwrap_or_default();
+
+        // Like the webpack build, the chunk exports the handler's `routeModule`,
+        // or registers it for the edge runtime.
+        let node_chunk = match runtime {
+            NextRuntime::NodeJs => node_build_context.bootstrap_entry_chunk(
+                app_route_entry(
+                    node_module,
+                    node_build_context.node_asset_context(),
+                    app_dir,
+                ),
+                chunk_path,
+            ),
+            NextRuntime::Edge => edge_build_context.bootstrap_entry_chunk(
+                edge_app_route_entry(
+                    edge_build_context.node_module(source_asset, reference_type),
+                    edge_build_context.node_asset_context(),
+                    app_dir,
+                    edge_app_route_function_name(&original_path),
+                ),
+                chunk_path,
+            ),
+        };
+
+        chunks.push(
+            AppRouteChunk {
+                pathname: pathname_for_path(app_dir, app_dir.join(&original_path), PathType::App),
+                original_path: StringVc::cell(original_path),
+                runtime,
+                node_chunk,
+            }
+            .cell(),
+        );
+    }
+
+    Ok(AppRouteChunksVc::cell(chunks))
+}
+
+/// Returns the name of the edge function of the edge route handler at
+/// `original_path` within the app directory, in the middleware manifest and in
+/// the edge runtime.
+pub(crate) fn edge_app_route_function_name(original_path: &str) -> String {
+    format!("app/{original_path}")
+}
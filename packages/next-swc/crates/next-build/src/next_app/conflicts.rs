@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use next_core::{
+    app_structure::{find_app_dir_if_enabled, get_entrypoints, Entrypoint, LoaderTreeVc},
+    next_config::NextConfigVc,
+    pages_structure::{PagesDirectoryStructureVc, PagesStructureVc},
+    pathname_for_path, PathType,
+};
+use turbo_tasks::{primitives::StringVc, CompletionVc};
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::core::issue::{Issue, IssueSeverity, IssueSeverityVc, IssueVc},
+};
+
+/// Emits an error for every pathname that is claimed by both a file in the
+/// `pages` directory and a file in the `app` directory, as the router couldn't
+/// tell which one to serve.
+#[turbo_tasks::function]
+pub async fn check_app_and_pages_conflicts(
+    pages_structure: PagesStructureVc,
+    next_router_root: FileSystemPathVc,
+    project_root: FileSystemPathVc,
+    next_config: NextConfigVc,
+) -> Result<CompletionVc> {
+    let Some(app_dir) = *find_app_dir_if_enabled(project_root, next_config).await? else {
+        return Ok(CompletionVc::immutable());
+    };
+
+    let pages_structure = pages_structure.await?;
+    let mut pages = HashMap::new();
+    let mut directories: Vec<PagesDirectoryStructureVc> = pages_structure
+        .api
+        .iter()
+        .chain(pages_structure.pages.iter())
+        .copied()
+        .collect();
+    while let Some(directory) = directories.pop() {
+        let directory = directory.await?;
+        for item in directory.items.iter() {
+            let item = item.await?;
+            let pathname =
+                pathname_for_path(next_router_root, item.next_router_path, PathType::Page).await?;
+            pages.insert(pathname.clone_value(), item.project_path);
+        }
+        directories.extend(directory.children.iter().copied());
+    }
+
+    let entrypoints = get_entrypoints(app_dir, next_config.page_extensions()).await?;
+    for (pathname, entrypoint) in entrypoints.iter() {
+        let Some(&page_path) = pages.get(pathname) else {
+            continue;
+        };
+        let app_path = match *entrypoint {
+            Entrypoint::AppPage { loader_tree } => match find_page(loader_tree).await? {
+                Some(path) => path,
+                None => continue,
+            },
+            Entrypoint::AppRoute { path } => path,
+        };
+        ConflictingAppAndPageIssue {
+            project_root,
+            pathname: pathname.clone(),
+            page_path,
+            app_path,
+        }
+        .cell()
+        .as_issue()
+        .emit();
+    }
+
+    Ok(CompletionVc::new())
+}
+
+/// Returns the `page` file at the leaf of the `children` parallel route.
+async fn find_page(mut loader_tree: LoaderTreeVc) -> Result<Option<FileSystemPathVc>> {
+    loop {
+        let tree = loader_tree.await?;
+        if let Some(page) = tree.components.await?.page {
+            return Ok(Some(page));
+        }
+        match tree.parallel_routes.get("children") {
+            Some(&child) => loader_tree = child,
+            None => return Ok(None),
+        }
+    }
+}
+
+#[turbo_tasks::value(shared)]
+struct ConflictingAppAndPageIssue {
+    project_root: FileSystemPathVc,
+    pathname: String,
+    page_path: FileSystemPathVc,
+    app_path: FileSystemPathVc,
+}
+
+#[turbo_tasks::value_impl]
+impl Issue for ConflictingAppAndPageIssue {
+    #[turbo_tasks::function]
+    fn severity(&self) -> IssueSeverityVc {
+        IssueSeverity::Error.into()
+    }
+
+    #[turbo_tasks::function]
+    fn title(&self) -> StringVc {
+        StringVc::cell(
+            "Conflicting app and page file was found, please remove the conflicting files to \
+             continue"
+                .to_string(),
+        )
+    }
+
+    #[turbo_tasks::function]
+    fn category(&self) -> StringVc {
+        StringVc::cell("next app".to_string())
+    }
+
+    #[turbo_tasks::function]
+    fn context(&self) -> FileSystemPathVc {
+        self.app_path
+    }
+
+    #[turbo_tasks::function]
+    async fn description(&self) -> Result<StringVc> {
+        Ok(StringVc::cell(format!(
+            "\"{}\" - \"{}\" both resolve to {}",
+            relative_to(self.project_root, self.page_path).await?,
+            relative_to(self.project_root, self.app_path).await?,
+            self.pathname
+        )))
+    }
+}
+
+async fn relative_to(root: FileSystemPathVc, path: FileSystemPathVc) -> Result<String> {
+    let path = path.await?;
+    Ok(root
+        .await?
+        .get_path_to(&path)
+        .map_or_else(|| path.path.clone(), str::to_string))
+}
@@ -0,0 +1,2 @@
+pub(crate) mod app_route_chunks;
+pub(crate) mod conflicts;
@@ -1,5 +1,5 @@
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     env::current_dir,
     path::{PathBuf, MAIN_SEPARATOR},
 };
@@ -7,8 +7,9 @@ use std::{
 use anyhow::{anyhow, Context, Result};
 use dunce::canonicalize;
 use next_core::{
-    self, next_config::load_next_config, pages_structure::find_pages_structure,
-    turbopack::ecmascript::utils::StringifyJs, url_node::get_sorted_routes,
+    self, mode::NextMode, next_config::load_next_config, next_edge::wasm::edge_wasm_binding_name,
+    pages_structure::find_pages_structure, turbopack::ecmascript::utils::StringifyJs,
+    url_node::get_sorted_routes, NextRuntime,
 };
 use serde::Serialize;
 use turbo_tasks::{
@@ -17,7 +18,9 @@ use turbo_tasks::{
     ValueToString,
 };
 use turbopack_binding::{
-    turbo::tasks_fs::{DiskFileSystemVc, FileContent, FileSystem, FileSystemPathVc, FileSystemVc},
+    turbo::tasks_fs::{
+        rope::Rope, DiskFileSystemVc, File, FileContent, FileSystem, FileSystemPathVc, FileSystemVc,
+    },
     turbopack::{
         cli_utils::issue::{ConsoleUiVc, LogOptions},
         core::{
@@ -30,22 +33,43 @@ use turbopack_binding::{
         dev::DevChunkingContextVc,
         env::dotenv::load_env,
         node::execution_context::ExecutionContextVc,
-        turbopack::evaluate_context::node_build_environment,
+        turbopack::{evaluate_context::node_build_environment, transition::TransitionsByNameVc},
     },
 };
 
 use crate::{
     build_options::{BuildContext, BuildOptions},
+    chunk_collector::{ChunkCollector, ChunkKind},
+    compression,
     manifests::{
-        AppBuildManifest, AppPathsManifest, BuildManifest, ClientBuildManifest,
-        ClientCssReferenceManifest, ClientReferenceManifest, FontManifest, MiddlewaresManifest,
-        NextFontManifest, PagesManifest, ReactLoadableManifest, ServerReferenceManifest,
+        subresource_integrity, AppBuildManifest, AppPathsManifest, AssetBinding, BuildManifest,
+        ChunkRoutesManifest, ClientBuildManifest, ClientCssReferenceManifest,
+        ClientReferenceManifest, EdgeFunctionDefinition, FontManifest, MiddlewareMatcher,
+        MiddlewaresManifest, MiddlewaresManifestV2, NextFontManifest, PagesManifest,
+        ReactLoadableManifest, ReactLoadableManifestEntry, RoutesManifest, ServerReferenceManifest,
+    },
+    next_app::{
+        app_route_chunks::{edge_app_route_function_name, get_app_route_chunks},
+        conflicts::check_app_and_pages_conflicts,
+    },
+    next_middleware::{
+        get_middleware_chunk, middleware_matcher_regexp, page_matcher_source, MiddlewareChunk,
+        OptionMiddlewareChunkVc, MIDDLEWARE_NAME,
+    },
+    next_pages::page_chunks::{
+        edge_function_name, get_page_chunks, get_pages_client_bootstrap, PageRouteFilter,
+        PagesOverrides,
     },
-    next_pages::page_chunks::get_page_chunks,
 };
 
+/// The output directory, relative to the project directory, when none is
+/// configured.
+const DEFAULT_OUTPUT_DIR: &str = ".next";
+
 #[turbo_tasks::function]
-pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Result<CompletionVc> {
+pub(crate) async fn next_build(
+    options: TransientInstance<BuildOptions>,
+) -> Result<OptionBuildPlanVc> {
     let project_root = options
         .dir
         .as_ref()
@@ -80,9 +104,21 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     let issue_reporter: IssueReporterVc =
         ConsoleUiVc::new(TransientInstance::new(log_options)).into();
     let node_fs = node_fs(&project_root, issue_reporter);
-    let node_root = node_fs.root().join(".next");
+    // Everything else is written relative to these roots, so relocating them
+    // moves the whole output tree.
+    let node_root = node_fs.root().join(
+        options
+            .node_output_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_OUTPUT_DIR),
+    );
     let client_fs = client_fs(&project_root, issue_reporter);
-    let client_root = client_fs.root().join(".next");
+    let client_root = client_fs.root().join(
+        options
+            .client_output_dir
+            .as_deref()
+            .unwrap_or(DEFAULT_OUTPUT_DIR),
+    );
     // TODO(alexkirsz) This should accept a URL for assetPrefix.
     // let client_public_fs = VirtualFileSystemVc::new();
     // let client_public_root = client_public_fs.root();
@@ -118,16 +154,71 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         next_router_root,
         project_root,
         execution_context,
+        NextMode::Build,
         node_root,
         client_root,
         env,
         browserslist_query,
         next_config,
         ServerAddrVc::empty(),
-    );
+        PageRouteFilter {
+            prefixes: options.routes.clone(),
+        }
+        .cell(),
+        PagesOverrides {
+            app: options
+                .custom_app
+                .as_ref()
+                .map(|path| workspace_fs.root().join(path)),
+            document: options
+                .custom_document
+                .as_ref()
+                .map(|path| workspace_fs.root().join(path)),
+        }
+        .cell(),
+        TransitionsByNameVc::cell(HashMap::new()),
+    )
+    // Pages are processed in pathname order, so the manifests and the emitted
+    // output don't depend on the order the pages directory was read in.
+    .sorted_chunks();
 
     handle_issues(page_chunks, issue_reporter).await?;
 
+    let client_bootstrap = get_pages_client_bootstrap(
+        pages_structure,
+        project_root,
+        execution_context,
+        NextMode::Build,
+        client_root,
+        env,
+        browserslist_query,
+        next_config,
+        TransitionsByNameVc::cell(HashMap::new()),
+    );
+
+    handle_issues(client_bootstrap, issue_reporter).await?;
+
+    let app_route_chunks = get_app_route_chunks(
+        project_root,
+        execution_context,
+        node_root,
+        env,
+        next_config,
+        ServerAddrVc::empty(),
+    );
+
+    handle_issues(app_route_chunks, issue_reporter).await?;
+
+    let conflicts =
+        check_app_and_pages_conflicts(pages_structure, next_router_root, project_root, next_config);
+
+    handle_issues(conflicts, issue_reporter).await?;
+
+    let middleware_chunk =
+        get_middleware_chunk(project_root, execution_context, node_root, env, next_config);
+
+    handle_issues(middleware_chunk, issue_reporter).await?;
+
     let filter_pages = std::env::var("NEXT_TURBO_FILTER_PAGES");
     let filter_pages = filter_pages
         .as_ref()
@@ -138,91 +229,131 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
     {
         // Client manifest.
         let mut build_manifest: BuildManifest = Default::default();
+        // Pages that load each client chunk.
+        let mut chunk_routes_manifest: ChunkRoutesManifest = Default::default();
         // Server manifest.
         let mut pages_manifest: PagesManifest = Default::default();
+        // Server manifest for the app directory.
+        let mut app_paths_manifest: AppPathsManifest = Default::default();
+        // Edge functions manifest.
+        let mut middlewares_manifest: MiddlewaresManifestV2 = Default::default();
+        // Font files to preload for each page.
+        let mut next_font_manifest: NextFontManifest = Default::default();
+        // Client chunks of each `next/dynamic` import.
+        let mut react_loadable_manifest: ReactLoadableManifest = Default::default();
+        // Status codes of the custom error pages, by pathname.
+        let mut error_pages: BTreeMap<String, u16> = BTreeMap::new();
 
-        let build_manifest_path = client_root.join("build-manifest.json");
+        // The build manifest is read by the server, but lists client chunks
+        // relative to the client root.
+        let build_manifest_path = node_root.join("build-manifest.json");
         let pages_manifest_path = node_root.join("server/pages-manifest.json");
 
-        let page_chunks_and_url = page_chunks
-            .await?
-            .iter()
-            .map(|page_chunk| async move {
-                let page_chunk = page_chunk.await?;
+        {
+            let build_manifest_dir_path = client_root.await?;
+            let pages_manifest_dir_path = pages_manifest_path.parent().await?;
+            // Hashing every client chunk is only worth it when integrity is requested.
+            let sri_algorithm = next_config
+                .await?
+                .experimental
+                .sri
+                .as_ref()
+                .and_then(|sri| sri.algorithm.clone());
+
+            // Chunks are written as soon as they are found, unless this is a dry run
+            // that shouldn't write anything.
+            let mut all_chunks = if options.dry_run {
+                ChunkCollector::default()
+            } else {
+                let precompression_level = options.precompression_level;
+                ChunkCollector::streaming(Box::new(move |asset, kind| {
+                    Box::pin(emit_with_precompression(
+                        asset,
+                        kind,
+                        client_root,
+                        precompression_level,
+                    ))
+                }))
+            };
+            // Maps each pathname to the chunks of the `next/dynamic` imports reachable
+            // from it.
+            let mut dynamic_chunks_by_pathname: HashMap<String, Vec<String>> = HashMap::new();
+            let mut edge_pages = vec![];
+
+            // TODO(alexkirsz) We want all assets to emit them to the output directory, but
+            // we only want runtime assets in the manifest. Furthermore, the pages
+            // manifest (server) only wants a single runtime asset, so we need to
+            // bundle node assets somewhat.
+            //
+            // Pages are collected and emitted one at a time, so that the assets of all
+            // pages are never held in memory at once.
+            for &page_chunk_vc in page_chunks.await?.iter() {
+                let page_chunk = page_chunk_vc.await?;
                 let pathname = page_chunk.pathname.await?;
 
                 if let Some(filter_pages) = &filter_pages {
                     if !filter_pages.contains(pathname.as_str()) {
-                        return Ok(None);
+                        continue;
                     }
                 }
 
-                // We can't use partitioning for client assets as client assets might be created
-                // by non-client assets referred from client assets.
-                // Although this should perhaps be enforced by Turbopack semantics.
-                let all_node_assets: Vec<_> = all_assets_from_entry(page_chunk.node_chunk)
-                    .await?
-                    .iter()
-                    .map(|asset| async move {
-                        Ok((
-                            asset.ident().path().await?.is_inside(&*node_root.await?),
-                            asset,
-                        ))
-                    })
-                    .try_join()
-                    .await?
-                    .into_iter()
-                    .filter_map(|(is_inside, asset)| if is_inside { Some(*asset) } else { None })
-                    .collect();
-
+                let node_chunk = page_chunk.node_chunk;
                 let client_chunks = page_chunk.client_chunks;
+                let dynamic_entries = page_chunk.dynamic_entries;
+                let config = page_chunk.config.clone();
+                let status_code = page_chunk.status_code;
 
-                // We can't use partitioning for client assets as client assets might be created
-                // by non-client assets referred from client assets.
-                // Although this should perhaps be enforced by Turbopack semantics.
-                let all_client_assets: Vec<_> = all_assets_from_entries(client_chunks)
-                    .await?
-                    .iter()
-                    .map(|asset| async move {
-                        Ok((
-                            asset.ident().path().await?.is_inside(&*client_root.await?),
-                            asset,
-                        ))
-                    })
-                    .try_join()
-                    .await?
-                    .into_iter()
-                    .filter_map(|(is_inside, asset)| if is_inside { Some(*asset) } else { None })
-                    .collect();
-
-                Ok(Some((
-                    pathname,
-                    page_chunk.node_chunk,
-                    all_node_assets,
-                    client_chunks,
-                    all_client_assets,
-                )))
-            })
-            .try_join()
-            .await?
-            .into_iter()
-            .flatten()
-            .collect::<Vec<_>>();
+                let assets: Result<_> = async {
+                    // We can't use partitioning for client assets as client assets might be
+                    // created by non-client assets referred from client assets.
+                    // Although this should perhaps be enforced by Turbopack semantics.
+                    let all_node_assets: Vec<_> = all_assets_from_entry(node_chunk)
+                        .await?
+                        .iter()
+                        .map(|asset| async move {
+                            Ok((
+                                asset.ident().path().await?.is_inside(&*node_root.await?),
+                                asset,
+                            ))
+                        })
+                        .try_join()
+                        .await?
+                        .into_iter()
+                        .filter_map(
+                            |(is_inside, asset)| if is_inside { Some(*asset) } else { None },
+                        )
+                        .collect();
 
-        {
-            let build_manifest_dir_path = build_manifest_path.parent().await?;
-            let pages_manifest_dir_path = pages_manifest_path.parent().await?;
+                    let all_client_assets: Vec<_> = all_assets_from_entries(client_chunks)
+                        .await?
+                        .iter()
+                        .map(|asset| async move {
+                            Ok((
+                                asset.ident().path().await?.is_inside(&*client_root.await?),
+                                asset,
+                            ))
+                        })
+                        .try_join()
+                        .await?
+                        .into_iter()
+                        .filter_map(
+                            |(is_inside, asset)| if is_inside { Some(*asset) } else { None },
+                        )
+                        .collect();
 
-            let mut deduplicated_node_assets = HashMap::new();
-            let mut deduplicated_client_assets = HashMap::new();
+                    Ok((all_node_assets, all_client_assets))
+                }
+                .await;
+                let (all_node_assets, all_client_assets) = match assets {
+                    Ok(assets) => assets,
+                    Err(err) => {
+                        return Err(err.context(format!(
+                            "failed to collect the assets of {}",
+                            page_chunk_vc.to_string().await?
+                        )))
+                    }
+                };
 
-            // TODO(alexkirsz) We want all assets to emit them to the output directory, but
-            // we only want runtime assets in the manifest. Furthermore, the pages
-            // manifest (server) only wants a single runtime asset, so we need to
-            // bundle node assets somewhat.
-            for (pathname, node_chunk, all_node_assets, client_chunks, all_client_assets) in
-                page_chunks_and_url
-            {
                 tracing::debug!("pathname: {}", pathname.to_string(),);
                 tracing::debug!(
                     "node chunk: {}",
@@ -241,24 +372,23 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                         .join("\n")
                 );
 
-                // TODO(alexkirsz) Deduplication should not happen at this level, but
-                // right now we have chunks with the same path being generated
-                // from different entrypoints, and writing them multiple times causes
-                // an infinite invalidation loop.
-                deduplicated_node_assets.extend(
-                    all_node_assets
-                        .into_iter()
-                        .map(|asset| async move { Ok((asset.ident().path().to_string().await?, asset)) })
-                        .try_join()
-                        .await?,
-                );
-                deduplicated_client_assets.extend(
-                    all_client_assets
-                        .into_iter()
-                        .map(|asset| async move { Ok((asset.ident().path().to_string().await?, asset)) })
-                        .try_join()
-                        .await?
-                );
+                let mut font_files = vec![];
+                for asset in &all_client_assets {
+                    let asset_path = asset.ident().path().await?;
+                    if let Some(asset_path) = build_manifest_dir_path.get_path_to(&asset_path) {
+                        if is_font_file(asset_path) {
+                            font_files.push(asset_path.to_string());
+                        }
+                    }
+                }
+                if !font_files.is_empty() {
+                    next_font_manifest
+                        .pages
+                        .insert(pathname.clone_value(), font_files);
+                }
+
+                all_chunks.extend(all_node_assets).await?;
+                all_chunks.extend(all_client_assets).await?;
 
                 let build_manifest_pages_entry = build_manifest
                     .pages
@@ -268,9 +398,72 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                     let chunk_path = chunk.ident().path().await?;
                     if let Some(asset_path) = build_manifest_dir_path.get_path_to(&chunk_path) {
                         build_manifest_pages_entry.push(asset_path.to_string());
+                        chunk_routes_manifest.insert(asset_path, &pathname);
+
+                        if let Some(algorithm) = &sri_algorithm {
+                            if let FileContent::Content(file) =
+                                &*chunk.content().file_content().await?
+                            {
+                                build_manifest.integrity.insert(
+                                    asset_path.to_string(),
+                                    subresource_integrity(algorithm, &file.content().to_bytes()?)?,
+                                );
+                            }
+                        }
                     }
                 }
 
+                let dynamic_chunks = dynamic_chunks_by_pathname
+                    .entry(pathname.clone_value())
+                    .or_default();
+                for (ident, dynamic_entry) in dynamic_entries.with_client_entry_idents().await? {
+                    let chunk_group_id = dynamic_entry.chunk_group_id().await?;
+                    tracing::debug!(
+                        "dynamic entry: {} (chunk group {})",
+                        ident.to_string().await?,
+                        chunk_group_id
+                    );
+                    let mut files = vec![];
+                    for chunk in dynamic_entry.chunks().await?.iter() {
+                        let chunk_path = chunk.ident().path().await?;
+                        if let Some(asset_path) = build_manifest_dir_path.get_path_to(&chunk_path) {
+                            files.push(asset_path.to_string());
+                            dynamic_chunks.push(asset_path.to_string());
+                            chunk_routes_manifest.insert(asset_path, &pathname);
+                        }
+                    }
+                    // Several pages can reach the same dynamic import, its chunks are
+                    // the same for all of them.
+                    react_loadable_manifest.manifest.insert(
+                        chunk_group_id.clone_value(),
+                        ReactLoadableManifestEntry {
+                            id: chunk_group_id.clone_value(),
+                            files,
+                        },
+                    );
+                }
+
+                if config.amp {
+                    build_manifest.amp_first_pages.push(pathname.clone_value());
+                }
+
+                if let Some(status_code) = status_code {
+                    error_pages.insert(pathname.clone_value(), status_code);
+                }
+
+                // Edge pages can't be loaded by the Node.js server, they are edge
+                // functions instead.
+                if config.runtime == NextRuntime::Edge {
+                    edge_pages.push(EdgePage {
+                        name: edge_function_name(&pathname),
+                        page: pathname.clone_value(),
+                        pathname: pathname.clone_value(),
+                        edge_chunk: node_chunk,
+                        regions: config.regions,
+                    });
+                    continue;
+                }
+
                 let chunk_path = node_chunk.ident().path().await?;
                 if let Some(asset_path) = pages_manifest_dir_path.get_path_to(&chunk_path) {
                     pages_manifest
@@ -279,55 +472,123 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
                 }
             }
 
-            tracing::debug!(
-                "all node assets: {}",
-                deduplicated_node_assets
-                    .values()
-                    .map(|asset| async move {
-                        Ok(format!("  - {}", asset.ident().path().to_string().await?))
-                    })
-                    .try_join()
-                    .await?
-                    .join("\n")
-            );
-            deduplicated_node_assets
-                .into_values()
-                .map(|asset| async move {
-                    emit(asset).await?;
-                    Ok(())
-                })
-                .try_join()
+            tracing::debug!("dynamic chunks: {:#?}", dynamic_chunks_by_pathname);
+
+            // `_document` renders these before the chunks of the page.
+            let client_bootstrap = client_bootstrap.await?;
+            for chunk in client_bootstrap.main_chunks.await?.iter() {
+                let chunk_path = chunk.ident().path().await?;
+                if let Some(asset_path) = build_manifest_dir_path.get_path_to(&chunk_path) {
+                    build_manifest.root_main_files.push(asset_path.to_string());
+                }
+            }
+            all_chunks
+                .extend(
+                    all_assets_from_entries(client_bootstrap.main_chunks)
+                        .await?
+                        .iter()
+                        .copied()
+                        .collect(),
+                )
                 .await?;
 
-            tracing::debug!(
-                "all client assets: {}",
-                deduplicated_client_assets
-                    .values()
+            let polyfill_path = client_bootstrap.polyfill_asset.ident().path().await?;
+            if let Some(asset_path) = build_manifest_dir_path.get_path_to(&polyfill_path) {
+                build_manifest.polyfill_files.push(asset_path.to_string());
+            }
+            all_chunks
+                .extend(vec![client_bootstrap.polyfill_asset])
+                .await?;
+
+            // Route handlers only run on the server, so they only contribute to the
+            // app paths manifest, and to the middleware manifest when they run in
+            // the edge runtime.
+            for app_route_chunk in app_route_chunks.await?.iter() {
+                let app_route_chunk = app_route_chunk.await?;
+                tracing::debug!("route handler: {}", app_route_chunk.pathname.await?);
+
+                if app_route_chunk.runtime == NextRuntime::Edge {
+                    let original_path = app_route_chunk.original_path.await?;
+                    edge_pages.push(EdgePage {
+                        name: edge_app_route_function_name(&original_path),
+                        page: format!("/{original_path}"),
+                        pathname: app_route_chunk.pathname.await?.clone_value(),
+                        edge_chunk: app_route_chunk.node_chunk,
+                        regions: None,
+                    });
+                }
+
+                let all_node_assets = all_assets_from_entry(app_route_chunk.node_chunk)
+                    .await?
+                    .iter()
                     .map(|asset| async move {
-                        Ok(format!("  - {}", asset.ident().path().to_string().await?))
+                        Ok((
+                            asset.ident().path().await?.is_inside(&*node_root.await?),
+                            *asset,
+                        ))
                     })
                     .try_join()
                     .await?
-                    .join("\n")
-            );
-            deduplicated_client_assets
-                .into_values()
-                .map(|asset| async move {
-                    emit(asset).await?;
-                    Ok(())
-                })
-                .try_join()
-                .await?;
+                    .into_iter()
+                    .filter_map(|(is_inside, asset)| is_inside.then_some(asset))
+                    .collect();
+                all_chunks.extend(all_node_assets).await?;
+
+                let chunk_path = app_route_chunk.node_chunk.ident().path().await?;
+                if let Some(asset_path) = pages_manifest_dir_path.get_path_to(&chunk_path) {
+                    app_paths_manifest.node_server_app_paths.pages.insert(
+                        app_route_chunk.pathname.await?.clone_value(),
+                        asset_path.to_string(),
+                    );
+                }
+            }
+
+            compute_middleware_chunks(
+                middleware_chunk,
+                node_root,
+                &mut all_chunks,
+                &mut middlewares_manifest,
+            )
+            .await?;
+            compute_edge_functions_manifest(
+                edge_pages,
+                node_root,
+                &mut all_chunks,
+                &mut middlewares_manifest,
+            )
+            .await?;
+
+            if options.dry_run {
+                return Ok(OptionBuildPlanVc::cell(Some(
+                    BuildPlan {
+                        client: build_manifest
+                            .pages
+                            .iter()
+                            .map(|(pathname, chunks)| (pathname.clone(), chunks.clone()))
+                            .collect(),
+                        server: pages_manifest.pages.clone(),
+                    }
+                    .cell(),
+                )));
+            }
+
+            tracing::debug!("all chunks:\n{}", all_chunks.describe());
         }
 
         write_placeholder_manifest(
-            &MiddlewaresManifest::default(),
+            &MiddlewaresManifest::MiddlewaresManifestV2(middlewares_manifest),
             node_root,
             "server/middleware-manifest.json",
         )
         .await?;
         write_placeholder_manifest(
-            &NextFontManifest::default(),
+            &chunk_routes_manifest,
+            node_root,
+            "chunk-routes-manifest.json",
+        )
+        .await?;
+        write_placeholder_manifest(
+            &next_font_manifest,
             node_root,
             "server/next-font-manifest.json",
         )
@@ -339,7 +600,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         )
         .await?;
         write_placeholder_manifest(
-            &AppPathsManifest::default(),
+            &app_paths_manifest,
             node_root,
             "server/app-paths-manifest.json",
         )
@@ -350,8 +611,13 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
             "server/server-reference-manifest.json",
         )
         .await?;
+        // App pages aren't built yet, so there are no client references.
+        let mut client_reference_manifest = ClientReferenceManifest::default();
+        if options.prefix_manifest_chunk_paths {
+            client_reference_manifest.apply_asset_prefix(&next_config.asset_prefix().await?);
+        }
         write_placeholder_manifest(
-            &ClientReferenceManifest::default(),
+            &client_reference_manifest,
             node_root,
             "server/client-reference-manifest.json",
         )
@@ -363,7 +629,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
         )
         .await?;
         write_placeholder_manifest(
-            &ReactLoadableManifest::default(),
+            &react_loadable_manifest,
             node_root,
             "react-loadable-manifest.json",
         )
@@ -382,7 +648,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
             let ssg_manifest_path = format!("static/{build_id}/_ssgManifest.js");
 
-            let ssg_manifest_fs_path = node_root.join(&ssg_manifest_path);
+            let ssg_manifest_fs_path = client_root.join(&ssg_manifest_path);
             ssg_manifest_fs_path
                 .write(
                     FileContent::Content(
@@ -397,7 +663,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
             build_manifest.low_priority_files.push(ssg_manifest_path);
 
             let sorted_pages =
-                get_sorted_routes(&pages_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
+                get_sorted_routes(&build_manifest.pages.keys().cloned().collect::<Vec<_>>())?;
 
             let app_dependencies: HashSet<&str> = pages_manifest
                 .pages
@@ -434,7 +700,7 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
 
             let client_manifest_path = format!("static/{build_id}/_buildManifest.js");
 
-            let client_manifest_fs_path = node_root.join(&client_manifest_path);
+            let client_manifest_fs_path = client_root.join(&client_manifest_path);
             client_manifest_fs_path
                 .write(
                     FileContent::Content(
@@ -452,6 +718,38 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
             build_manifest.low_priority_files.push(client_manifest_path);
         }
 
+        // When building through Next.js, it writes the routes manifest itself,
+        // so only emit it for standalone builds. App routes are matched like
+        // pages.
+        if options.build_context.is_none() {
+            let sorted_pages = get_sorted_routes(
+                &build_manifest
+                    .pages
+                    .keys()
+                    .chain(app_paths_manifest.node_server_app_paths.pages.keys())
+                    .cloned()
+                    .collect::<Vec<_>>(),
+            )?;
+            let rewrites = next_config.rewrites().await?;
+            let routes_manifest = RoutesManifest::new(
+                next_config.base_path().await?.clone_value(),
+                &rewrites,
+                &sorted_pages,
+                &error_pages,
+            );
+            write_placeholder_manifest(&routes_manifest, node_root, "routes-manifest.json").await?;
+        }
+
+        if let Some(hook) = &options.pages_manifest_hook {
+            (hook.0)(&mut pages_manifest).context("the pages manifest hook failed")?;
+        }
+
+        // The client build manifest above keeps the relative paths, the runtime
+        // prepends the asset prefix to those itself.
+        if options.prefix_manifest_chunk_paths {
+            build_manifest.apply_asset_prefix(&next_config.asset_prefix().await?);
+        }
+
         // TODO(alexkirsz) These manifests should be assets.
         let build_manifest_contents = serde_json::to_string_pretty(&build_manifest)?;
         let pages_manifest_contents = serde_json::to_string_pretty(&pages_manifest)?;
@@ -464,7 +762,170 @@ pub(crate) async fn next_build(options: TransientInstance<BuildOptions>) -> Resu
             .await?;
     }
 
-    Ok(CompletionVc::immutable())
+    Ok(OptionBuildPlanVc::cell(None))
+}
+
+/// Adds the middleware's edge chunks to `all_chunks` and records its matchers
+/// and files into the middleware manifest.
+async fn compute_middleware_chunks(
+    middleware_chunk: OptionMiddlewareChunkVc,
+    node_root: FileSystemPathVc,
+    all_chunks: &mut ChunkCollector,
+    middlewares_manifest: &mut MiddlewaresManifestV2,
+) -> Result<()> {
+    let Some(middleware_chunk) = *middleware_chunk.await? else {
+        return Ok(());
+    };
+    let MiddlewareChunk {
+        ref matchers,
+        edge_chunk,
+    } = *middleware_chunk.await?;
+
+    let (files, wasm) = collect_edge_chunk_files(edge_chunk, node_root, all_chunks).await?;
+
+    let matchers = match matchers {
+        Some(matchers) => matchers
+            .iter()
+            .map(|source| {
+                Ok(MiddlewareMatcher {
+                    regexp: middleware_matcher_regexp(source)?,
+                    original_source: source.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?,
+        // Without matchers, the middleware runs for every request.
+        None => vec![MiddlewareMatcher {
+            regexp: "^.*$".to_string(),
+            original_source: "/:path*".to_string(),
+        }],
+    };
+
+    middlewares_manifest.sorted_middleware.push("/".to_string());
+    middlewares_manifest.middleware.insert(
+        "/".to_string(),
+        EdgeFunctionDefinition {
+            files,
+            name: MIDDLEWARE_NAME.to_string(),
+            page: "/".to_string(),
+            matchers,
+            wasm,
+            ..Default::default()
+        },
+    );
+
+    Ok(())
+}
+
+/// A page or app route handler built for the edge runtime.
+struct EdgePage {
+    /// The name of the page's edge function, see [edge_function_name] and
+    /// [edge_app_route_function_name].
+    name: String,
+    /// The page the edge function is listed as. This is the pathname for
+    /// pages, and the path of the route handler, like `/api/hello/route`, for
+    /// app route handlers.
+    page: String,
+    /// The pathname of the page. Like the pages manifest, the middleware
+    /// manifest doesn't include the `basePath`.
+    pathname: String,
+    edge_chunk: AssetVc,
+    regions: Option<Vec<String>>,
+}
+
+/// Adds the chunks of the edge runtime pages to `all_chunks` and records each
+/// of them as an edge function in the middleware manifest.
+async fn compute_edge_functions_manifest(
+    edge_pages: Vec<EdgePage>,
+    node_root: FileSystemPathVc,
+    all_chunks: &mut ChunkCollector,
+    middlewares_manifest: &mut MiddlewaresManifestV2,
+) -> Result<()> {
+    for EdgePage {
+        name,
+        page,
+        pathname,
+        edge_chunk,
+        regions,
+    } in edge_pages
+    {
+        let (files, wasm) = collect_edge_chunk_files(edge_chunk, node_root, all_chunks).await?;
+        let source = page_matcher_source(&pathname);
+        let matchers = vec![MiddlewareMatcher {
+            regexp: middleware_matcher_regexp(&source)?,
+            original_source: source,
+        }];
+        middlewares_manifest.functions.insert(
+            page.clone(),
+            EdgeFunctionDefinition {
+                files,
+                name,
+                page,
+                matchers,
+                wasm,
+                regions,
+                ..Default::default()
+            },
+        );
+    }
+
+    Ok(())
+}
+
+/// Adds every asset reachable from `edge_chunk` to `all_chunks` and returns the
+/// JS files and the WebAssembly bindings to list in its edge function
+/// definition.
+async fn collect_edge_chunk_files(
+    edge_chunk: AssetVc,
+    node_root: FileSystemPathVc,
+    all_chunks: &mut ChunkCollector,
+) -> Result<(Vec<String>, Vec<AssetBinding>)> {
+    let node_root = node_root.await?;
+
+    // Chunks are listed in reverse topological order so that the entry chunk is
+    // evaluated last.
+    let mut files = vec![];
+    let mut wasm = vec![];
+    let mut edge_assets = vec![];
+    for asset in all_assets_from_entry(edge_chunk).await?.iter() {
+        let asset_path = asset.ident().path().await?;
+        let Some(relative_path) = node_root.get_path_to(&asset_path) else {
+            continue;
+        };
+        if relative_path.ends_with(".js") {
+            files.push(relative_path.to_string());
+        } else if relative_path.ends_with(".wasm") {
+            // The binding name must match the one the `.wasm` import reads from.
+            if let FileContent::Content(file) = &*asset.content().file_content().await? {
+                wasm.push(AssetBinding {
+                    name: edge_wasm_binding_name(&file.content().to_bytes()?),
+                    file_path: relative_path.to_string(),
+                });
+            }
+        }
+        edge_assets.push(*asset);
+    }
+    all_chunks.extend(edge_assets).await?;
+
+    Ok((files, wasm))
+}
+
+/// The output a dry run would have produced for each page.
+#[turbo_tasks::value(shared)]
+#[derive(Clone, Debug)]
+pub struct BuildPlan {
+    /// The client chunks of each page, relative to the client output root.
+    pub client: BTreeMap<String, Vec<String>>,
+    /// The server entry of each page, relative to the server output root.
+    pub server: BTreeMap<String, String>,
+}
+
+/// The plan of a dry run, or `None` when the build was emitted.
+#[turbo_tasks::value(transparent)]
+pub(crate) struct OptionBuildPlan(Option<BuildPlanVc>);
+
+/// Whether the path is a font file that can be preloaded.
+fn is_font_file(path: &str) -> bool {
+    ChunkKind::from_path(path) == ChunkKind::Font
 }
 
 #[turbo_tasks::function]
@@ -472,6 +933,51 @@ fn emit(asset: AssetVc) -> CompletionVc {
     asset.content().write(asset.ident().path())
 }
 
+/// Writes `asset` and, when `precompression_level` is set and `kind` is
+/// worth compressing, its compressed siblings.
+async fn emit_with_precompression(
+    asset: AssetVc,
+    kind: ChunkKind,
+    client_root: FileSystemPathVc,
+    precompression_level: Option<u32>,
+) -> Result<()> {
+    emit(asset).await?;
+    if let Some(level) = precompression_level.filter(|_| kind.should_precompress()) {
+        emit_precompressed(asset, client_root, level).await?;
+    }
+    Ok(())
+}
+
+/// Writes gzip and brotli compressed siblings of `asset` when it is a client
+/// chunk, so that the server can serve them without compressing on the fly.
+/// The caller decides which kinds of chunks are precompressed.
+#[turbo_tasks::function]
+async fn emit_precompressed(
+    asset: AssetVc,
+    client_root: FileSystemPathVc,
+    level: u32,
+) -> Result<CompletionVc> {
+    let path = asset.ident().path();
+    if client_root.await?.get_path_to(&*path.await?).is_none() {
+        return Ok(CompletionVc::immutable());
+    }
+    let FileContent::Content(file) = &*asset.content().file_content().await? else {
+        return Ok(CompletionVc::immutable());
+    };
+    let content = file.content().to_bytes()?;
+
+    let variants = [
+        (".gz", compression::gzip(&content, level)?),
+        (".br", compression::brotli(&content, level)?),
+    ];
+    for (extension, compressed) in variants {
+        path.append(extension)
+            .write(FileContent::Content(File::from(Rope::from(compressed))).cell())
+            .await?;
+    }
+    Ok(CompletionVc::new())
+}
+
 #[turbo_tasks::function]
 async fn workspace_fs(
     workspace_root: &str,
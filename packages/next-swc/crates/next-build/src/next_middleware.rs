@@ -0,0 +1,328 @@
+use std::collections::HashMap;
+
+use anyhow::{bail, Result};
+use next_core::{
+    mode::NextMode,
+    next_config::NextConfigVc,
+    next_edge::context::{
+        get_edge_compile_time_info, get_edge_module_options_context,
+        get_edge_resolve_options_context, should_minify_edge_chunks,
+    },
+    next_server::ServerContextType,
+    parse_config_from_source,
+    router::middleware_files,
+};
+use turbopack_binding::{
+    turbo::{tasks::Value, tasks_env::ProcessEnvVc, tasks_fs::FileSystemPathVc},
+    turbopack::{
+        core::{
+            asset::AssetVc,
+            reference_type::{EntryReferenceSubType, ReferenceType},
+            resolve::{find_context_file, FindContextFileResult},
+            source_asset::SourceAssetVc,
+        },
+        node::execution_context::ExecutionContextVc,
+        turbopack::{transition::TransitionsByNameVc, ModuleAssetContextVc},
+    },
+};
+
+use crate::next_pages::{
+    node_context::PagesBuildNodeContextVc, page_chunks::get_node_runtime_entries,
+};
+
+/// The name of the middleware's edge function, in the middleware manifest and
+/// in the edge runtime.
+pub(crate) const MIDDLEWARE_NAME: &str = "middleware";
+
+/// The middleware of a project, built for the edge runtime.
+#[turbo_tasks::value]
+pub struct MiddlewareChunk {
+    /// The matcher sources from the middleware's `config` export, if any.
+    pub matchers: Option<Vec<String>>,
+    /// The edge entry chunk.
+    pub edge_chunk: AssetVc,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct OptionMiddlewareChunk(Option<MiddlewareChunkVc>);
+
+/// Returns the middleware chunk, if the project has a middleware file.
+#[turbo_tasks::function]
+pub async fn get_middleware_chunk(
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    node_root: FileSystemPathVc,
+    env: ProcessEnvVc,
+    next_config: NextConfigVc,
+) -> Result<OptionMiddlewareChunkVc> {
+    let FindContextFileResult::Found(middleware_path, _) = *find_context_file(
+        project_root,
+        middleware_files(next_config.page_extensions()),
+    )
+    .await?
+    else {
+        return Ok(OptionMiddlewareChunkVc::cell(None));
+    };
+
+    let ty = Value::new(ServerContextType::Middleware);
+
+    // There is no server to talk to in a build.
+    let edge_compile_time_info =
+        get_edge_compile_time_info(NextMode::Build, project_root, None, None);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_root,
+        ty,
+        NextMode::Build,
+        next_config,
+        execution_context,
+    );
+    let edge_module_options_context = get_edge_module_options_context(
+        project_root,
+        execution_context,
+        ty,
+        NextMode::Build,
+        next_config,
+    );
+
+    let edge_asset_context = ModuleAssetContextVc::new(
+        TransitionsByNameVc::cell(HashMap::new()),
+        edge_compile_time_info,
+        edge_module_options_context,
+        edge_resolve_options_context,
+    )
+    .into();
+
+    let edge_build_context = PagesBuildNodeContextVc::new(
+        project_root,
+        node_root,
+        edge_asset_context,
+        get_node_runtime_entries(project_root, env, next_config),
+        should_minify_edge_chunks(NextMode::Build, next_config),
+    );
+
+    let source_asset = SourceAssetVc::new(middleware_path).into();
+    let reference_type = Value::new(ReferenceType::Entry(EntryReferenceSubType::Undefined));
+
+    let config = parse_config_from_source(
+        edge_build_context.node_module(source_asset, reference_type.clone()),
+    )
+    .await?;
+
+    Ok(OptionMiddlewareChunkVc::cell(Some(
+        MiddlewareChunk {
+            matchers: config.matcher.clone(),
+            edge_chunk: edge_build_context.edge_entry_chunk(
+                source_asset,
+                project_root,
+                MIDDLEWARE_NAME.to_string(),
+                "server/middleware.js".to_string(),
+                reference_type,
+            ),
+        }
+        .cell(),
+    )))
+}
+
+/// Prefix Next.js adds to middleware matchers so that they also match
+/// `/_next/data/<build id>` requests.
+const NEXT_DATA_PREFIX: &str = r"(?:\/(_next\/data\/[^/]{1,}))?";
+
+/// Converts a middleware matcher source, as written in the middleware's
+/// `config` export, into the regular expression the Next.js runtime matches
+/// requests against.
+///
+/// This follows `path-to-regexp` semantics for named (`:name`) and unnamed
+/// (`(pattern)`) parameters with an optional `?`, `*` or `+` modifier.
+pub(crate) fn middleware_matcher_regexp(source: &str) -> Result<String> {
+    if !source.starts_with('/') {
+        bail!("middleware matcher `{}` must start with `/`", source);
+    }
+
+    if source == "/" {
+        return Ok(format!(
+            r"^{NEXT_DATA_PREFIX}(?:\/(\/?index|\/?index\.json))?[\/#\?]?$"
+        ));
+    }
+
+    let mut regexp = format!("^{NEXT_DATA_PREFIX}");
+    let mut literal = String::new();
+    let mut chars = source.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        let name = if c == ':' {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if !(c.is_alphanumeric() || c == '_') {
+                    break;
+                }
+                name.push(c);
+                chars.next();
+            }
+            if name.is_empty() {
+                bail!("missing parameter name in middleware matcher `{}`", source);
+            }
+            Some(name)
+        } else if c == '(' {
+            None
+        } else {
+            literal.push(c);
+            continue;
+        };
+
+        let pattern = if name.is_none() || chars.peek() == Some(&'(') {
+            if name.is_some() {
+                chars.next();
+            }
+            let mut pattern = String::new();
+            let mut depth = 1;
+            loop {
+                let Some(c) = chars.next() else {
+                    bail!("unbalanced pattern in middleware matcher `{}`", source);
+                };
+                match c {
+                    '\\' => {
+                        pattern.push(c);
+                        if let Some(c) = chars.next() {
+                            pattern.push(c);
+                        }
+                        continue;
+                    }
+                    '(' => depth += 1,
+                    ')' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+                pattern.push(c);
+            }
+            pattern
+        } else {
+            r"[^\/#\?]+?".to_string()
+        };
+
+        let prefix = match literal.chars().last() {
+            Some(c @ ('/' | '.')) => {
+                literal.pop();
+                escape_regexp(&c.to_string())
+            }
+            _ => String::new(),
+        };
+        regexp.push_str(&escape_regexp(&literal));
+        literal.clear();
+
+        let modifier = chars.next_if(|c| matches!(c, '?' | '*' | '+'));
+        match modifier {
+            Some(modifier @ ('*' | '+')) => {
+                regexp.push_str(&format!(
+                    "(?:{prefix}((?:{pattern})(?:{prefix}(?:{pattern}))*))"
+                ));
+                if modifier == '*' {
+                    regexp.push('?');
+                }
+            }
+            Some(_) => regexp.push_str(&format!("(?:{prefix}({pattern}))?")),
+            None => regexp.push_str(&format!("(?:{prefix}({pattern}))")),
+        }
+    }
+
+    regexp.push_str(&escape_regexp(&literal));
+    regexp.push_str(r"(.json)?[\/#\?]?$");
+
+    Ok(regexp)
+}
+
+/// Escapes the characters `path-to-regexp` treats as special.
+fn escape_regexp(literal: &str) -> String {
+    let mut escaped = String::with_capacity(literal.len());
+    for c in literal.chars() {
+        if ".+*?=^!:${}()[]|/\\".contains(c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Converts the pathname of a page, like `/blog/[slug]`, into a matcher source
+/// for [`middleware_matcher_regexp`], like `/blog/:slug`.
+pub(crate) fn page_matcher_source(pathname: &str) -> String {
+    pathname
+        .split('/')
+        .map(|segment| {
+            if let Some(name) = segment
+                .strip_prefix("[[...")
+                .and_then(|s| s.strip_suffix("]]"))
+            {
+                format!(":{name}*")
+            } else if let Some(name) = segment
+                .strip_prefix("[...")
+                .and_then(|s| s.strip_suffix(']'))
+            {
+                format!(":{name}+")
+            } else if let Some(name) = segment.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+                format!(":{name}")
+            } else {
+                segment.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use next_core::next_edge::entry::edge_entry_key;
+
+    use super::{middleware_matcher_regexp, page_matcher_source, MIDDLEWARE_NAME};
+
+    #[test]
+    fn registers_middleware_entry() {
+        assert_eq!(edge_entry_key(MIDDLEWARE_NAME), "middleware_middleware");
+    }
+
+    #[test]
+    fn named_parameters() {
+        assert_eq!(
+            middleware_matcher_regexp("/about/:path*").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/about(?:\/((?:[^\/#\?]+?)(?:\/(?:[^\/#\?]+?))*))?(.json)?[\/#\?]?$"
+        );
+        assert_eq!(
+            middleware_matcher_regexp("/blog/:slug").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?\/blog(?:\/([^\/#\?]+?))(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn unnamed_pattern() {
+        assert_eq!(
+            middleware_matcher_regexp("/((?!api|_next/static).*)").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?(?:\/((?!api|_next/static).*))(.json)?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn root() {
+        assert_eq!(
+            middleware_matcher_regexp("/").unwrap(),
+            r"^(?:\/(_next\/data\/[^/]{1,}))?(?:\/(\/?index|\/?index\.json))?[\/#\?]?$"
+        );
+    }
+
+    #[test]
+    fn page_sources() {
+        assert_eq!(page_matcher_source("/"), "/");
+        assert_eq!(page_matcher_source("/api/hello"), "/api/hello");
+        assert_eq!(page_matcher_source("/blog/[slug]"), "/blog/:slug");
+        assert_eq!(page_matcher_source("/docs/[...path]"), "/docs/:path+");
+        assert_eq!(page_matcher_source("/shop/[[...path]]"), "/shop/:path*");
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(middleware_matcher_regexp("about").is_err());
+        assert!(middleware_matcher_regexp("/(foo").is_err());
+    }
+}
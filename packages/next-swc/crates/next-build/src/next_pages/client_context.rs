@@ -6,14 +6,17 @@ use next_core::{
 use turbopack_binding::{
     turbo::{
         tasks::{primitives::StringVc, Value},
-        tasks_fs::FileSystemPathVc,
+        tasks_fs::{FileContent, FileSystemPathVc},
+        tasks_hash::hash_xxh3_hash64,
     },
     turbopack::{
         core::{
-            asset::AssetVc,
+            asset::{Asset, AssetVc},
             chunk::{ChunkableAsset, ChunkingContext, ChunkingContextVc},
             context::{AssetContext, AssetContextVc},
-            reference_type::ReferenceType,
+            reference_type::{EntryReferenceSubType, ReferenceType},
+            resolve::{parse::RequestVc, pattern::QueryMapVc},
+            virtual_asset::VirtualAssetVc,
         },
         dev::DevChunkingContextVc,
         ecmascript::EcmascriptModuleAssetVc,
@@ -46,8 +49,10 @@ impl PagesBuildClientContextVc {
         .cell()
     }
 
+    /// Returns the chunking context the client chunks of pages are generated
+    /// with.
     #[turbo_tasks::function]
-    async fn client_chunking_context(self) -> Result<ChunkingContextVc> {
+    pub async fn client_chunking_context(self) -> Result<ChunkingContextVc> {
         let this = self.await?;
 
         Ok(DevChunkingContextVc::builder(
@@ -60,6 +65,71 @@ impl PagesBuildClientContextVc {
         .build())
     }
 
+    /// Returns the chunks that evaluate the client runtime entries, which every
+    /// page needs before its own chunks.
+    #[turbo_tasks::function]
+    pub async fn runtime_chunks(self) -> Result<AssetsVc> {
+        let this = self.await?;
+
+        let Some(&last_entry) = this.client_runtime_entries.await?.last() else {
+            return Ok(AssetsVc::cell(vec![]));
+        };
+        let Some(entry_module_asset) = EcmascriptModuleAssetVc::resolve_from(last_entry).await?
+        else {
+            bail!("Expected an EcmaScript module asset");
+        };
+
+        let client_chunking_context = self.client_chunking_context();
+
+        Ok(client_chunking_context.evaluated_chunk_group(
+            entry_module_asset.as_root_chunk(client_chunking_context),
+            this.client_runtime_entries,
+        ))
+    }
+
+    /// Returns the polyfills Next.js loads in browsers without ES module
+    /// support. Like in the webpack build, they are already bundled, and are
+    /// copied to `static/chunks/polyfills-[hash].js` as they are.
+    #[turbo_tasks::function]
+    pub async fn polyfill_asset(self) -> Result<AssetVc> {
+        let this = self.await?;
+
+        let origin = this.project_root.join("_");
+        let reference_type = Value::new(ReferenceType::Entry(EntryReferenceSubType::Page));
+        let Some(polyfill_asset) = this
+            .client_asset_context
+            .resolve_asset(
+                origin,
+                RequestVc::module(
+                    "next".to_string(),
+                    Value::new("/dist/build/polyfills/polyfill-nomodule".to_string().into()),
+                    QueryMapVc::none(),
+                ),
+                this.client_asset_context
+                    .resolve_options(origin, reference_type.clone()),
+                reference_type,
+            )
+            .primary_assets()
+            .await?
+            .first()
+            .copied()
+        else {
+            bail!("the Next.js polyfills could not be found");
+        };
+        let polyfill_content = polyfill_asset.ident().path().read();
+        let FileContent::Content(file) = &*polyfill_content.await? else {
+            bail!("the Next.js polyfills could not be read");
+        };
+        let hash = hash_xxh3_hash64(file.content().to_bytes()?);
+
+        Ok(VirtualAssetVc::new(
+            this.client_root
+                .join(&format!("static/chunks/polyfills-{hash:016x}.js")),
+            polyfill_content.into(),
+        )
+        .into())
+    }
+
     #[turbo_tasks::function]
     pub async fn client_chunk(
         self,
@@ -1,10 +1,14 @@
 use anyhow::{bail, Result};
-use next_core::{next_client::RuntimeEntriesVc, turbopack::core::chunk::EvaluatableAssetsVc};
-use turbo_tasks::primitives::StringVc;
+use next_core::{
+    next_client::RuntimeEntriesVc,
+    next_edge::entry::edge_function_entry,
+    turbopack::core::chunk::{EvaluatableAssetVc, EvaluatableAssetsVc},
+};
+use turbo_tasks::primitives::{BoolVc, StringVc};
 use turbopack_binding::{
     turbo::{tasks::Value, tasks_fs::FileSystemPathVc},
     turbopack::{
-        build::BuildChunkingContextVc,
+        build::{BuildChunkingContextVc, MinifyType},
         core::{
             asset::AssetVc,
             context::{AssetContext, AssetContextVc},
@@ -21,6 +25,9 @@ pub(crate) struct PagesBuildNodeContext {
     node_root: FileSystemPathVc,
     node_asset_context: AssetContextVc,
     node_runtime_entries: EvaluatableAssetsVc,
+    /// Whether the chunks are minified. Only edge chunks are, Node.js chunks
+    /// stay readable for stack traces.
+    minify: BoolVc,
 }
 
 #[turbo_tasks::value_impl]
@@ -31,12 +38,14 @@ impl PagesBuildNodeContextVc {
         node_root: FileSystemPathVc,
         node_asset_context: AssetContextVc,
         node_runtime_entries: RuntimeEntriesVc,
+        minify: BoolVc,
     ) -> PagesBuildNodeContextVc {
         PagesBuildNodeContext {
             project_root,
             node_root,
             node_asset_context,
             node_runtime_entries: node_runtime_entries.resolve_entries(node_asset_context),
+            minify,
         }
         .cell()
     }
@@ -74,8 +83,10 @@ impl PagesBuildNodeContextVc {
         Ok(asset)
     }
 
+    /// Returns the chunking context the server chunks of pages are generated
+    /// with.
     #[turbo_tasks::function]
-    async fn node_chunking_context(self) -> Result<BuildChunkingContextVc> {
+    pub async fn node_chunking_context(self) -> Result<BuildChunkingContextVc> {
         let this = self.await?;
 
         Ok(BuildChunkingContextVc::builder(
@@ -85,21 +96,62 @@ impl PagesBuildNodeContextVc {
             this.node_root.join("server/assets"),
             this.node_asset_context.compile_time_info().environment(),
         )
+        .minify_type(if *this.minify.await? {
+            MinifyType::Minify
+        } else {
+            MinifyType::NoMinify
+        })
         .build())
     }
 
+    /// Returns the asset context modules are processed with.
+    #[turbo_tasks::function]
+    pub async fn node_asset_context(self) -> Result<AssetContextVc> {
+        Ok(self.await?.node_asset_context)
+    }
+
+    /// Returns the page module as processed by the Node.js asset context.
+    #[turbo_tasks::function]
+    pub async fn node_module(
+        self,
+        source_asset: AssetVc,
+        reference_type: Value<ReferenceType>,
+    ) -> Result<AssetVc> {
+        let this = self.await?;
+
+        Ok(this
+            .node_asset_context
+            .process(source_asset, reference_type))
+    }
+
     #[turbo_tasks::function]
     pub async fn node_chunk(
         self,
         source_asset: AssetVc,
         original_path: StringVc,
         reference_type: Value<ReferenceType>,
+    ) -> Result<AssetVc> {
+        let original_path = original_path.await?;
+
+        Ok(self.entry_chunk(
+            source_asset,
+            format!("server/pages/{original_path}.js"),
+            reference_type,
+        ))
+    }
+
+    /// Returns an entry chunk for the module at `chunk_path`, relative to the
+    /// node root.
+    #[turbo_tasks::function]
+    pub async fn entry_chunk(
+        self,
+        source_asset: AssetVc,
+        chunk_path: String,
+        reference_type: Value<ReferenceType>,
     ) -> Result<AssetVc> {
         let this = self.await?;
 
-        let node_asset_page = this
-            .node_asset_context
-            .process(source_asset, reference_type);
+        let node_asset_page = self.node_module(source_asset, reference_type);
 
         let Some(node_module_asset) =
             EcmascriptChunkPlaceableVc::resolve_from(node_asset_page).await?
@@ -107,14 +159,57 @@ impl PagesBuildNodeContextVc {
             bail!("Expected an EcmaScript module asset");
         };
 
-        let original_path = original_path.await?;
-
         let chunking_context = self.node_chunking_context();
         Ok(chunking_context.generate_entry_chunk(
-            this.node_root
-                .join(&format!("server/pages/{original_path}.js")),
+            this.node_root.join(&chunk_path),
             node_module_asset,
             this.node_runtime_entries,
         ))
     }
+
+    /// Returns an entry chunk for the edge function `name`, at `chunk_path`
+    /// relative to the node root. The module is wrapped in the edge
+    /// bootstrap, which registers it for the edge runtime.
+    #[turbo_tasks::function]
+    pub async fn edge_entry_chunk(
+        self,
+        source_asset: AssetVc,
+        base_path: FileSystemPathVc,
+        name: String,
+        chunk_path: String,
+        reference_type: Value<ReferenceType>,
+    ) -> Result<AssetVc> {
+        let this = self.await?;
+
+        let edge_entry = edge_function_entry(
+            self.node_module(source_asset, reference_type),
+            this.node_asset_context,
+            base_path,
+            name,
+        );
+
+        Ok(self.bootstrap_entry_chunk(edge_entry, chunk_path))
+    }
+
+    /// Returns an entry chunk for `entry`, a module wrapped in a bootstrap,
+    /// at `chunk_path` relative to the node root.
+    #[turbo_tasks::function]
+    pub async fn bootstrap_entry_chunk(
+        self,
+        entry: EvaluatableAssetVc,
+        chunk_path: String,
+    ) -> Result<AssetVc> {
+        let this = self.await?;
+
+        let Some(entry) = EcmascriptChunkPlaceableVc::resolve_from(entry).await? else {
+            bail!("Expected an EcmaScript module asset");
+        };
+
+        let chunking_context = self.node_chunking_context();
+        Ok(chunking_context.generate_entry_chunk(
+            this.node_root.join(&chunk_path),
+            entry,
+            this.node_runtime_entries,
+        ))
+    }
 }
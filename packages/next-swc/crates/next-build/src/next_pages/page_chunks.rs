@@ -7,8 +7,12 @@ use next_core::{
         get_client_resolve_options_context, get_client_runtime_entries, ClientContextType,
         RuntimeEntriesVc, RuntimeEntry,
     },
-    next_client_chunks::NextClientChunksTransitionVc,
+    next_client_chunks::{visit_dynamic, NextClientChunksTransitionVc, NextDynamicEntriesVc},
     next_config::NextConfigVc,
+    next_edge::context::{
+        get_edge_compile_time_info, get_edge_module_options_context,
+        get_edge_resolve_options_context, should_minify_edge_chunks,
+    },
     next_server::{
         get_server_compile_time_info, get_server_module_options_context,
         get_server_resolve_options_context, ServerContextType,
@@ -17,20 +21,22 @@ use next_core::{
         PagesDirectoryStructure, PagesDirectoryStructureVc, PagesStructure, PagesStructureItem,
         PagesStructureVc,
     },
-    pathname_for_path,
+    parse_config_from_source, pathname_for_path,
     turbopack::core::asset::AssetsVc,
-    PathType,
+    NextRuntime, PathType,
 };
-use turbo_tasks::ValueToString;
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{primitives::BoolVc, trace::TraceRawVcs, TryJoinIterExt, ValueToString};
 use turbopack_binding::{
     turbo::{
         tasks::{primitives::StringVc, Value},
         tasks_env::ProcessEnvVc,
-        tasks_fs::FileSystemPathVc,
+        tasks_fs::{FileSystemEntryType, FileSystemPathVc},
     },
     turbopack::{
         core::{
-            asset::AssetVc,
+            asset::{Asset, AssetVc},
+            chunk::ChunkingContextVc,
             context::AssetContextVc,
             environment::ServerAddrVc,
             reference_type::{EntryReferenceSubType, ReferenceType},
@@ -53,77 +59,115 @@ impl PageChunksVc {
     pub fn empty() -> Self {
         PageChunks(vec![]).cell()
     }
+
+    /// Returns the page chunks sorted by pathname, so that the list doesn't
+    /// depend on the order the pages structure was traversed in.
+    #[turbo_tasks::function]
+    pub async fn sorted_chunks(self) -> Result<Self> {
+        let mut chunks = self
+            .await?
+            .iter()
+            .map(|chunk| async move { Ok((chunk.await?.pathname.await?, *chunk)) })
+            .try_join()
+            .await?;
+        chunks.sort_by(|(a, _), (b, _)| a.as_str().cmp(b.as_str()));
+        Ok(PageChunksVc::cell(
+            chunks.into_iter().map(|(_, chunk)| chunk).collect(),
+        ))
+    }
+}
+
+/// Restricts page chunk generation to a subset of the `pages` and `api`
+/// routes.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Default)]
+pub struct PageRouteFilter {
+    /// Route prefixes to build, like `/admin` or `/admin/**`. When empty, all
+    /// routes are built.
+    pub prefixes: Vec<String>,
+}
+
+/// Files to build the `_app` and `_document` pages from instead of the ones
+/// found in the pages directory.
+#[turbo_tasks::value(shared)]
+#[derive(Default)]
+pub struct PagesOverrides {
+    pub app: Option<FileSystemPathVc>,
+    pub document: Option<FileSystemPathVc>,
+}
+
+impl PageRouteFilter {
+    /// Whether the page at the router path `route` should be built.
+    fn includes_route(&self, route: &str) -> bool {
+        self.prefixes.is_empty()
+            || self
+                .prefixes
+                .iter()
+                .any(|prefix| is_route_inside(route, normalize_route_prefix(prefix)))
+    }
+
+    /// Whether any page inside the router path `directory` could be built.
+    fn may_include_directory(&self, directory: &str) -> bool {
+        self.prefixes.is_empty()
+            || self.prefixes.iter().any(|prefix| {
+                let prefix = normalize_route_prefix(prefix);
+                is_route_inside(directory, prefix) || is_route_inside(prefix, directory)
+            })
+    }
+}
+
+/// Turns `/admin/**` and `/admin/` into the router path `admin`.
+fn normalize_route_prefix(prefix: &str) -> &str {
+    let prefix = prefix.trim_start_matches('/');
+    let prefix = prefix.strip_suffix("**").unwrap_or(prefix);
+    prefix.trim_end_matches('/')
+}
+
+fn is_route_inside(route: &str, prefix: &str) -> bool {
+    prefix.is_empty()
+        || route
+            .strip_prefix(prefix)
+            .map_or(false, |rest| rest.is_empty() || rest.starts_with('/'))
 }
 
 /// Returns a list of page chunks.
+///
+/// `mode` selects the defines, runtime entries and minification of the
+/// chunks, so that the dev server can compute them too.
+///
+/// `extra_transitions` are available in the page asset contexts next to the
+/// built-in `next-client-chunks` transition.
 #[turbo_tasks::function]
 pub async fn get_page_chunks(
     pages_structure: PagesStructureVc,
     next_router_root: FileSystemPathVc,
     project_root: FileSystemPathVc,
     execution_context: ExecutionContextVc,
+    mode: NextMode,
     node_root: FileSystemPathVc,
     client_root: FileSystemPathVc,
     env: ProcessEnvVc,
     browserslist_query: &str,
     next_config: NextConfigVc,
     node_addr: ServerAddrVc,
+    route_filter: PageRouteFilterVc,
+    overrides: PagesOverridesVc,
+    extra_transitions: TransitionsByNameVc,
 ) -> Result<PageChunksVc> {
-    let pages_dir = if let Some(pages) = pages_structure.await?.pages {
-        pages.project_path().resolve().await?
-    } else {
-        project_root.join("pages")
-    };
+    let pages_dir = get_pages_dir(pages_structure, project_root);
 
-    let mode = NextMode::Build;
-
-    let client_ty = Value::new(ClientContextType::Pages { pages_dir });
     let node_ty = Value::new(ServerContextType::Pages { pages_dir });
 
-    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
-
-    let transitions = TransitionsByNameVc::cell(
-        [(
-            // This is necessary for the next dynamic transform to work.
-            // TODO(alexkirsz) Should accept client chunking context? But how do we get this?
-            "next-client-chunks".to_string(),
-            NextClientChunksTransitionVc::new(
-                project_root,
-                execution_context,
-                client_ty,
-                mode,
-                client_root,
-                client_compile_time_info,
-                next_config,
-            )
-            .into(),
-        )]
-        .into_iter()
-        .collect(),
-    );
-
-    let client_module_options_context = get_client_module_options_context(
+    let transitions = get_page_transitions(
         project_root,
         execution_context,
-        client_compile_time_info.environment(),
-        client_ty,
-        mode,
-        next_config,
-    );
-    let client_resolve_options_context = get_client_resolve_options_context(
-        project_root,
-        client_ty,
         mode,
+        pages_dir,
+        client_root,
+        browserslist_query,
         next_config,
-        execution_context,
+        extra_transitions,
     );
-    let client_asset_context: AssetContextVc = ModuleAssetContextVc::new(
-        transitions,
-        client_compile_time_info,
-        client_module_options_context,
-        client_resolve_options_context,
-    )
-    .into();
 
     let node_compile_time_info = get_server_compile_time_info(mode, env, node_addr);
     let node_resolve_options_context = get_server_resolve_options_context(
@@ -149,46 +193,244 @@ pub async fn get_page_chunks(
     )
     .into();
 
-    let node_runtime_entries = get_node_runtime_entries(project_root, env, next_config);
-
-    let client_runtime_entries = get_client_runtime_entries(
+    // Pages opting into the edge runtime are built for the edge worker environment
+    // instead. There is no server to talk to in a build.
+    let edge_compile_time_info = get_edge_compile_time_info(mode, project_root, None, None);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
         project_root,
-        env,
-        client_ty,
+        node_ty,
         mode,
         next_config,
         execution_context,
     );
-    let client_runtime_entries = client_runtime_entries.resolve_entries(client_asset_context);
+
+    let edge_module_options_context = get_edge_module_options_context(
+        project_root,
+        execution_context,
+        node_ty,
+        mode,
+        next_config,
+    );
+
+    let edge_asset_context = ModuleAssetContextVc::new(
+        transitions,
+        edge_compile_time_info,
+        edge_module_options_context,
+        edge_resolve_options_context,
+    )
+    .into();
+
+    let node_runtime_entries = get_node_runtime_entries(project_root, env, next_config);
 
     let node_build_context = PagesBuildNodeContextVc::new(
         project_root,
         node_root,
         node_asset_context,
         node_runtime_entries,
+        BoolVc::cell(false),
     );
-    let client_build_context = PagesBuildClientContextVc::new(
+    let edge_build_context = PagesBuildNodeContextVc::new(
         project_root,
+        node_root,
+        edge_asset_context,
+        node_runtime_entries,
+        should_minify_edge_chunks(mode, next_config),
+    );
+    let client_build_context = get_pages_client_build_context(
+        project_root,
+        execution_context,
+        mode,
+        pages_dir,
         client_root,
-        client_asset_context,
-        client_runtime_entries,
+        env,
+        browserslist_query,
+        next_config,
+        transitions,
     );
 
     Ok(get_page_chunks_for_root_directory(
         node_build_context,
+        edge_build_context,
         client_build_context,
         pages_structure,
+        pages_dir,
         next_router_root,
+        route_filter,
+        overrides,
     ))
 }
 
+/// Returns the client context pages are built with.
+#[turbo_tasks::function]
+fn get_pages_client_build_context(
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    mode: NextMode,
+    pages_dir: FileSystemPathVc,
+    client_root: FileSystemPathVc,
+    env: ProcessEnvVc,
+    browserslist_query: &str,
+    next_config: NextConfigVc,
+    transitions: TransitionsByNameVc,
+) -> PagesBuildClientContextVc {
+    let client_ty = Value::new(ClientContextType::Pages { pages_dir });
+    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
+
+    let client_module_options_context = get_client_module_options_context(
+        project_root,
+        execution_context,
+        client_compile_time_info.environment(),
+        client_ty,
+        mode,
+        next_config,
+    );
+    let client_resolve_options_context = get_client_resolve_options_context(
+        project_root,
+        client_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
+    let client_asset_context: AssetContextVc = ModuleAssetContextVc::new(
+        transitions,
+        client_compile_time_info,
+        client_module_options_context,
+        client_resolve_options_context,
+    )
+    .into();
+
+    let client_runtime_entries = get_client_runtime_entries(
+        project_root,
+        env,
+        client_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
+    let client_runtime_entries = client_runtime_entries.resolve_entries(client_asset_context);
+
+    PagesBuildClientContextVc::new(
+        project_root,
+        client_root,
+        client_asset_context,
+        client_runtime_entries,
+    )
+}
+
+/// The client chunks that are loaded before the chunks of any page.
+#[turbo_tasks::value]
+pub struct PagesClientBootstrap {
+    /// The chunks evaluating the client runtime entries.
+    pub main_chunks: AssetsVc,
+    /// The polyfills for browsers without ES module support.
+    pub polyfill_asset: AssetVc,
+}
+
+/// Returns the client chunks shared by all pages. Takes the same arguments as
+/// [`get_page_chunks`], so that both use the same client context.
+#[turbo_tasks::function]
+pub fn get_pages_client_bootstrap(
+    pages_structure: PagesStructureVc,
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    mode: NextMode,
+    client_root: FileSystemPathVc,
+    env: ProcessEnvVc,
+    browserslist_query: &str,
+    next_config: NextConfigVc,
+    extra_transitions: TransitionsByNameVc,
+) -> PagesClientBootstrapVc {
+    let pages_dir = get_pages_dir(pages_structure, project_root);
+    let transitions = get_page_transitions(
+        project_root,
+        execution_context,
+        mode,
+        pages_dir,
+        client_root,
+        browserslist_query,
+        next_config,
+        extra_transitions,
+    );
+    let client_build_context = get_pages_client_build_context(
+        project_root,
+        execution_context,
+        mode,
+        pages_dir,
+        client_root,
+        env,
+        browserslist_query,
+        next_config,
+        transitions,
+    );
+
+    PagesClientBootstrap {
+        main_chunks: client_build_context.runtime_chunks(),
+        polyfill_asset: client_build_context.polyfill_asset(),
+    }
+    .cell()
+}
+
+#[turbo_tasks::function]
+async fn get_pages_dir(
+    pages_structure: PagesStructureVc,
+    project_root: FileSystemPathVc,
+) -> Result<FileSystemPathVc> {
+    Ok(if let Some(pages) = pages_structure.await?.pages {
+        pages.project_path().resolve().await?
+    } else {
+        project_root.join("pages")
+    })
+}
+
+/// Returns the transitions available in the page asset contexts.
+#[turbo_tasks::function]
+async fn get_page_transitions(
+    project_root: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    mode: NextMode,
+    pages_dir: FileSystemPathVc,
+    client_root: FileSystemPathVc,
+    browserslist_query: &str,
+    next_config: NextConfigVc,
+    extra_transitions: TransitionsByNameVc,
+) -> Result<TransitionsByNameVc> {
+    let client_ty = Value::new(ClientContextType::Pages { pages_dir });
+    let client_compile_time_info = get_client_compile_time_info(mode, browserslist_query);
+
+    let mut transitions = extra_transitions.await?.clone_value();
+    if transitions.contains_key("next-client-chunks") {
+        bail!("the \"next-client-chunks\" transition is reserved by Next.js");
+    }
+    transitions.insert(
+        // This is necessary for the next dynamic transform to work.
+        // TODO(alexkirsz) Should accept client chunking context? But how do we get this?
+        "next-client-chunks".to_string(),
+        NextClientChunksTransitionVc::new(
+            project_root,
+            execution_context,
+            client_ty,
+            mode,
+            client_root,
+            client_compile_time_info,
+            next_config,
+        )
+        .into(),
+    );
+    Ok(TransitionsByNameVc::cell(transitions))
+}
+
 #[turbo_tasks::function]
 async fn get_page_chunks_for_root_directory(
     node_build_context: PagesBuildNodeContextVc,
+    edge_build_context: PagesBuildNodeContextVc,
     client_build_context: PagesBuildClientContextVc,
     pages_structure: PagesStructureVc,
+    pages_dir: FileSystemPathVc,
     next_router_root: FileSystemPathVc,
+    route_filter: PageRouteFilterVc,
+    overrides: PagesOverridesVc,
 ) -> Result<PageChunksVc> {
+    let overrides = overrides.await?;
     let PagesStructure {
         app,
         document,
@@ -203,8 +445,10 @@ async fn get_page_chunks_for_root_directory(
     let app = app.await?;
     chunks.push(get_page_chunk_for_file(
         node_build_context,
+        edge_build_context,
         client_build_context,
-        SourceAssetVc::new(app.project_path).into(),
+        SourceAssetVc::new(overrides.app.unwrap_or(app.project_path)).into(),
+        pages_dir,
         next_router_root,
         app.next_router_path,
         app.original_path,
@@ -214,8 +458,10 @@ async fn get_page_chunks_for_root_directory(
     let document = document.await?;
     chunks.push(get_page_chunk_for_file(
         node_build_context,
+        edge_build_context,
         client_build_context,
-        SourceAssetVc::new(document.project_path).into(),
+        SourceAssetVc::new(overrides.document.unwrap_or(document.project_path)).into(),
+        pages_dir,
         next_router_root,
         document.next_router_path,
         document.original_path,
@@ -226,8 +472,10 @@ async fn get_page_chunks_for_root_directory(
     let error = error.await?;
     chunks.push(get_page_chunk_for_file(
         node_build_context,
+        edge_build_context,
         client_build_context,
         SourceAssetVc::new(error.project_path).into(),
+        pages_dir,
         next_router_root,
         error.next_router_path,
         error.original_path,
@@ -237,9 +485,12 @@ async fn get_page_chunks_for_root_directory(
         chunks.extend(
             get_page_chunks_for_directory(
                 node_build_context,
+                edge_build_context,
                 client_build_context,
                 api,
+                pages_dir,
                 next_router_root,
+                route_filter,
             )
             .await?
             .iter()
@@ -251,9 +502,12 @@ async fn get_page_chunks_for_root_directory(
         chunks.extend(
             get_page_chunks_for_directory(
                 node_build_context,
+                edge_build_context,
                 client_build_context,
                 pages,
+                pages_dir,
                 next_router_root,
+                route_filter,
             )
             .await?
             .iter()
@@ -267,27 +521,41 @@ async fn get_page_chunks_for_root_directory(
 #[turbo_tasks::function]
 async fn get_page_chunks_for_directory(
     node_build_context: PagesBuildNodeContextVc,
+    edge_build_context: PagesBuildNodeContextVc,
     client_build_context: PagesBuildClientContextVc,
     pages_structure: PagesDirectoryStructureVc,
+    pages_dir: FileSystemPathVc,
     next_router_root: FileSystemPathVc,
+    route_filter: PageRouteFilterVc,
 ) -> Result<PageChunksVc> {
     let PagesDirectoryStructure {
         ref items,
         ref children,
+        next_router_path,
         ..
     } = *pages_structure.await?;
     let mut chunks = vec![];
 
+    let filter = route_filter.await?;
+    if !filter.may_include_directory(&next_router_path.await?.path) {
+        return Ok(PageChunksVc::cell(chunks));
+    }
+
     for item in items.iter() {
         let PagesStructureItem {
             project_path,
             next_router_path,
             original_path,
         } = *item.await?;
+        if !filter.includes_route(&next_router_path.await?.path) {
+            continue;
+        }
         chunks.push(get_page_chunk_for_file(
             node_build_context,
+            edge_build_context,
             client_build_context,
             SourceAssetVc::new(project_path).into(),
+            pages_dir,
             next_router_root,
             next_router_path,
             original_path,
@@ -299,9 +567,12 @@ async fn get_page_chunks_for_directory(
             // TODO(alexkirsz) This should be a tree structure instead of a flattened list.
             get_page_chunks_for_directory(
                 node_build_context,
+                edge_build_context,
                 client_build_context,
                 *child,
+                pages_dir,
                 next_router_root,
+                route_filter,
             )
             .await?
             .iter()
@@ -312,22 +583,80 @@ async fn get_page_chunks_for_directory(
     Ok(PageChunksVc::cell(chunks))
 }
 
+/// The deployment settings a page declares through its `config` export.
+#[derive(Default, PartialEq, Eq, Clone, Debug, TraceRawVcs, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PageConfig {
+    pub runtime: NextRuntime,
+    pub regions: Option<Vec<String>>,
+    pub max_duration: Option<u32>,
+    /// Whether this is an AMP-first page, which doesn't load the client
+    /// runtime.
+    pub amp: bool,
+    /// Whether the page is rendered without any client-side JavaScript.
+    pub disable_runtime_js: bool,
+}
+
 /// A page chunk corresponding to some route.
 #[turbo_tasks::value]
 pub struct PageChunk {
     /// The pathname of the page.
     pub pathname: StringVc,
-    /// The Node.js chunk.
+    /// The settings from the page's `config` export.
+    pub config: PageConfig,
+    /// The server side chunk. For edge pages, this is built for the edge
+    /// runtime.
     pub node_chunk: AssetVc,
     /// The client chunks.
     pub client_chunks: AssetsVc,
+    /// The chunking context `node_chunk` was generated with, so the chunk
+    /// output can be reproduced when analyzing a page.
+    pub node_chunking_context: ChunkingContextVc,
+    /// The chunking context `client_chunks` were generated with.
+    pub client_chunking_context: ChunkingContextVc,
+    /// The `next/dynamic` entries reachable from the page.
+    pub dynamic_entries: NextDynamicEntriesVc,
+    /// The `<page>.data.json` file next to the page, if there is one, holding
+    /// static data for the page.
+    pub static_data: Option<FileSystemPathVc>,
+    /// The status code the page is rendered with when it is one of the custom
+    /// error pages, `/404` or `/500`.
+    pub status_code: Option<u16>,
+}
+
+#[turbo_tasks::value_impl]
+impl ValueToString for PageChunk {
+    /// Describes the page by its pathname and the idents of its chunks, for
+    /// use in logs and error context.
+    #[turbo_tasks::function]
+    async fn to_string(&self) -> Result<StringVc> {
+        let client_chunks = self
+            .client_chunks
+            .await?
+            .iter()
+            .map(|chunk| chunk.ident().to_string())
+            .try_join()
+            .await?;
+        Ok(StringVc::cell(format!(
+            "page {} (node: {}, client: [{}])",
+            self.pathname.await?,
+            self.node_chunk.ident().to_string().await?,
+            client_chunks
+                .iter()
+                .map(|ident| ident.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )))
+    }
 }
 
 #[turbo_tasks::function]
 async fn get_page_chunk_for_file(
     node_build_context: PagesBuildNodeContextVc,
+    edge_build_context: PagesBuildNodeContextVc,
     client_build_context: PagesBuildClientContextVc,
     page_asset: AssetVc,
+    pages_dir: FileSystemPathVc,
     next_router_root: FileSystemPathVc,
     next_router_path: FileSystemPathVc,
     original_path: FileSystemPathVc,
@@ -337,18 +666,89 @@ async fn get_page_chunk_for_file(
     let pathname = pathname_for_path(next_router_root, next_router_path, PathType::Page);
     let original_path = get_original_path(next_router_root, original_path);
 
+    let node_module = node_build_context.node_module(page_asset, reference_type.clone());
+    let source_config = parse_config_from_source(node_module).await?;
+    let config = PageConfig {
+        runtime: source_config.runtime,
+        regions: source_config.regions.clone(),
+        max_duration: source_config.max_duration,
+        amp: source_config.amp,
+        disable_runtime_js: source_config.disable_runtime_js,
+    };
+    // Edge pages are edge functions, which the edge bootstrap registers for the
+    // edge runtime.
+    let (server_build_context, node_chunk) = match config.runtime {
+        NextRuntime::NodeJs => (
+            node_build_context,
+            node_build_context.node_chunk(page_asset, original_path, reference_type.clone()),
+        ),
+        NextRuntime::Edge => (
+            edge_build_context,
+            edge_build_context.edge_entry_chunk(
+                page_asset,
+                pages_dir,
+                edge_function_name(&pathname.await?),
+                format!("server/pages/{}.js", original_path.await?),
+                reference_type.clone(),
+            ),
+        ),
+    };
+
+    // AMP-first pages and pages that opted out of the runtime are rendered
+    // without React hydration, so they don't ship any client chunks.
+    let client_chunks = if config.amp || config.disable_runtime_js {
+        AssetsVc::cell(vec![])
+    } else {
+        client_build_context.client_chunk(page_asset, pathname, reference_type.clone())
+    };
+
     Ok(PageChunk {
         pathname,
-        node_chunk: node_build_context.node_chunk(
-            page_asset,
-            original_path,
-            reference_type.clone(),
-        ),
-        client_chunks: client_build_context.client_chunk(page_asset, pathname, reference_type),
+        config,
+        node_chunk,
+        client_chunks,
+        node_chunking_context: server_build_context.node_chunking_context().into(),
+        client_chunking_context: client_build_context.client_chunking_context(),
+        dynamic_entries: visit_dynamic(node_module),
+        static_data: find_static_data(page_asset.ident().path()).await?,
+        status_code: error_page_status_code(&pathname.await?),
     }
     .cell())
 }
 
+/// Returns the name of the edge function of the edge page at `pathname`, in
+/// the middleware manifest and in the edge runtime.
+pub(crate) fn edge_function_name(pathname: &str) -> String {
+    format!("pages{}", if pathname == "/" { "/index" } else { pathname })
+}
+
+/// Returns the status code of the custom error page at `pathname`, if it is
+/// one.
+fn error_page_status_code(pathname: &str) -> Option<u16> {
+    match pathname {
+        "/404" => Some(404),
+        "/500" => Some(500),
+        _ => None,
+    }
+}
+
+/// Returns the `<page>.data.json` file co-located with the page file at
+/// `page_path`, if it exists.
+async fn find_static_data(page_path: FileSystemPathVc) -> Result<Option<FileSystemPathVc>> {
+    let Some(file_name) = static_data_file_name(page_path.await?.file_name()) else {
+        return Ok(None);
+    };
+    let static_data = page_path.parent().join(&file_name);
+    Ok(matches!(&*static_data.get_type().await?, FileSystemEntryType::File).then_some(static_data))
+}
+
+/// Turns the file name of a page, like `blog.tsx`, into the name of its static
+/// data file, like `blog.data.json`.
+fn static_data_file_name(page_file_name: &str) -> Option<String> {
+    let (stem, _) = page_file_name.rsplit_once('.')?;
+    (!stem.is_empty()).then(|| format!("{stem}.data.json"))
+}
+
 /// Returns the original path of a file within the Next.js router root.
 #[turbo_tasks::function]
 pub async fn get_original_path(
@@ -369,7 +769,7 @@ pub async fn get_original_path(
 }
 
 #[turbo_tasks::function]
-fn get_node_runtime_entries(
+pub(crate) fn get_node_runtime_entries(
     project_root: FileSystemPathVc,
     env: ProcessEnvVc,
     next_config: NextConfigVc,
@@ -381,3 +781,118 @@ fn get_node_runtime_entries(
 
     RuntimeEntriesVc::cell(node_runtime_entries)
 }
+
+#[cfg(test)]
+mod tests {
+    use anyhow::Result;
+    use next_core::{
+        mode::NextMode,
+        next_config::NextConfig,
+        next_edge::{context::get_edge_module_options_context, wasm::get_edge_wasm_rule},
+        next_server::ServerContextType,
+    };
+    use turbopack_binding::{
+        turbo::{
+            tasks::{run_once, TurboTasks, Value},
+            tasks_env::EnvMapVc,
+            tasks_memory::MemoryBackend,
+        },
+        turbopack::{
+            core::virtual_fs::VirtualFileSystemVc, dev::DevChunkingContextVc,
+            node::execution_context::ExecutionContextVc,
+            turbopack::evaluate_context::node_build_environment,
+        },
+    };
+
+    use super::{error_page_status_code, static_data_file_name, PageRouteFilter};
+
+    fn filter(prefixes: &[&str]) -> PageRouteFilter {
+        PageRouteFilter {
+            prefixes: prefixes.iter().map(|prefix| prefix.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn empty_filter_includes_everything() {
+        let filter = filter(&[]);
+        assert!(filter.includes_route(""));
+        assert!(filter.includes_route("blog/post"));
+        assert!(filter.may_include_directory("api"));
+    }
+
+    #[test]
+    fn prefix_filter() {
+        let filter = filter(&["/admin/**", "/api/admin"]);
+        assert!(filter.includes_route("admin"));
+        assert!(filter.includes_route("admin/users/[id]"));
+        assert!(filter.includes_route("api/admin/login"));
+        assert!(!filter.includes_route(""));
+        assert!(!filter.includes_route("administrator"));
+        assert!(!filter.includes_route("api/users"));
+
+        assert!(filter.may_include_directory(""));
+        assert!(filter.may_include_directory("api"));
+        assert!(filter.may_include_directory("admin/users"));
+        assert!(!filter.may_include_directory("blog"));
+    }
+
+    #[test]
+    fn static_data_file_names() {
+        assert_eq!(
+            static_data_file_name("blog.tsx").as_deref(),
+            Some("blog.data.json")
+        );
+        assert_eq!(
+            static_data_file_name("index.page.js").as_deref(),
+            Some("index.page.data.json")
+        );
+        assert_eq!(static_data_file_name("README"), None);
+        assert_eq!(static_data_file_name(".tsx"), None);
+    }
+
+    #[test]
+    fn error_page_status_codes() {
+        assert_eq!(error_page_status_code("/404"), Some(404));
+        assert_eq!(error_page_status_code("/500"), Some(500));
+        assert_eq!(error_page_status_code("/_error"), None);
+        assert_eq!(error_page_status_code("/blog/404"), None);
+    }
+
+    #[tokio::test]
+    async fn edge_pages_can_import_wasm() -> Result<()> {
+        crate::register();
+        let tt = TurboTasks::new(MemoryBackend::new(usize::MAX));
+        run_once(tt, async move {
+            let project_root = VirtualFileSystemVc::new().as_file_system().root();
+            let execution_context = ExecutionContextVc::new(
+                project_root,
+                DevChunkingContextVc::builder(
+                    project_root,
+                    project_root,
+                    project_root.join("chunks"),
+                    project_root.join("assets"),
+                    node_build_environment(),
+                )
+                .build(),
+                EnvMapVc::empty().into(),
+            );
+
+            let module_options_context = get_edge_module_options_context(
+                project_root,
+                execution_context,
+                Value::new(ServerContextType::Pages {
+                    pages_dir: project_root.join("pages"),
+                }),
+                NextMode::Build,
+                NextConfig::default().cell(),
+            )
+            .await?;
+            assert!(module_options_context
+                .custom_rules
+                .contains(&get_edge_wasm_rule()));
+
+            Ok(())
+        })
+        .await
+    }
+}
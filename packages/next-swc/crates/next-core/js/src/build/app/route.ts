@@ -0,0 +1,42 @@
+import 'next/dist/server/node-polyfill-headers'
+
+import RouteModule from 'ROUTE_MODULE'
+import * as userland from 'ENTRY'
+import { PAGE, PATHNAME, KIND } from 'BOOTSTRAP_CONFIG'
+
+// This mirrors the route handler entry of the webpack build, which the Next.js
+// server loads through its `routeModule` export.
+const routeModule = new RouteModule({
+  userland,
+  definition: {
+    page: `/${PAGE}`,
+    kind: KIND,
+    pathname: PATHNAME,
+    filename: PAGE.split('/').pop()!,
+    bundlePath: `app/${PAGE}`,
+  },
+  resolvedPagePath: `app/${PAGE}`,
+  nextConfigOutput: undefined,
+})
+
+// Pull out the exports the server hooks into. This should be eliminated once
+// the other routes have moved to the route module format.
+const {
+  requestAsyncStorage,
+  staticGenerationAsyncStorage,
+  serverHooks,
+  headerHooks,
+  staticGenerationBailout,
+} = routeModule
+
+const originalPathname = `/${PAGE}`
+
+export {
+  routeModule,
+  requestAsyncStorage,
+  staticGenerationAsyncStorage,
+  serverHooks,
+  headerHooks,
+  staticGenerationBailout,
+  originalPathname,
+}
@@ -0,0 +1,25 @@
+/**
+ * Makes the CSP nonce of the page available to the chunk loader, see
+ * `internal/load-chunk.ts`, so that dynamically loaded chunks are allowed
+ * under a strict `script-src 'nonce-...'` policy.
+ *
+ * The nonce is read from `__webpack_nonce__` when the app sets it, and falls
+ * back to the nonce of the script that loaded this runtime.
+ */
+
+declare global {
+  var __webpack_nonce__: string | undefined
+}
+
+function getNonce(): string | undefined {
+  if (globalThis.__webpack_nonce__) {
+    return globalThis.__webpack_nonce__
+  }
+  const script =
+    document.currentScript ?? document.querySelector('script[nonce]')
+  return (script as HTMLScriptElement | null)?.nonce || undefined
+}
+
+globalThis.__next_chunk_nonce__ = getNonce()
+
+export {}
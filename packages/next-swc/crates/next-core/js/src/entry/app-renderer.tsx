@@ -128,6 +128,7 @@ async function runOperation(renderData: RenderData) {
     pageConfig: {},
     reactLoadableManifest: {},
     nextConfigOutput: renderData.data?.nextConfigOutput,
+    serverActionsBodySizeLimit: renderData.data?.serverActionsBodySizeLimit,
   }
   const result = await renderToHTMLOrFlight(
     req,
@@ -0,0 +1,17 @@
+declare const $RefreshReg$: ((type: unknown, id: string) => void) | undefined
+
+import * as clientModule from 'CLIENT_REFERENCE_MODULE'
+
+// This wraps the client module in the client chunk of a client boundary.
+// Registering its exports lets fast refresh keep the state of the boundary
+// when the client module is edited.
+if (typeof $RefreshReg$ === 'function') {
+  for (const [name, value] of Object.entries(clientModule)) {
+    if (typeof value === 'function') {
+      $RefreshReg$(value, `client-reference ${name}`)
+    }
+  }
+}
+
+export * from 'CLIENT_REFERENCE_MODULE'
+export default clientModule.default
@@ -2,7 +2,7 @@ import { EdgeRouteModuleWrapper } from 'next/dist/server/web/edge-route-module-w
 
 import RouteModule from 'ROUTE_MODULE'
 import * as userland from 'ENTRY'
-import { PAGE, PATHNAME, KIND } from 'BOOTSTRAP_CONFIG'
+import { NAME, PAGE, PATHNAME, KIND } from 'BOOTSTRAP_CONFIG'
 
 // TODO: (wyattjoh) - perform the option construction in Rust to allow other modules to accept different options
 const routeModule = new RouteModule({
@@ -21,7 +21,7 @@ const routeModule = new RouteModule({
 
 // @ts-expect-error - exposed for edge support
 globalThis._ENTRIES = {
-  middleware_edge: {
+  [`middleware_${NAME}`]: {
     default: EdgeRouteModuleWrapper.wrap(routeModule, { page: `/${PAGE}` }),
   },
 }
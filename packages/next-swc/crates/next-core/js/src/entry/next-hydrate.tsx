@@ -8,20 +8,21 @@ import {
 } from 'next/dist/shared/lib/router/utils/querystring'
 import { formatWithValidation } from 'next/dist/shared/lib/router/utils/format-url'
 import { initializeHMR } from '../dev/client'
+import { loadChunk } from '../internal/load-chunk'
 import { subscribeToUpdate } from '@vercel/turbopack-ecmascript-runtime/dev/client/hmr-client'
 
 async function loadPageChunk(assetPrefix: string, chunkData: ChunkData) {
   if (typeof chunkData === 'string') {
     const fullPath = assetPrefix + chunkData
 
-    await __turbopack_load__(fullPath)
+    await loadChunk(fullPath)
   } else {
     let fullChunkData = {
       ...chunkData,
       path: assetPrefix + chunkData.path,
     }
 
-    await __turbopack_load__(fullChunkData)
+    await loadChunk(fullChunkData)
   }
 }
 
@@ -0,0 +1,36 @@
+/**
+ * Loads a chunk with the Turbopack chunk loader.
+ *
+ * When `experimental.chunkNonce` is enabled, the script and stylesheet tags the
+ * loader inserts carry the CSP nonce of the page. The loader inserts them
+ * synchronously, so only tags inserted while it runs get the nonce.
+ */
+export function loadChunk(chunkData: ChunkData): Promise<any> {
+  const nonce = globalThis.__next_chunk_nonce__
+  if (!nonce) {
+    return __turbopack_load__(chunkData)
+  }
+
+  const parents = [document.head, document.body]
+  for (const parent of parents) {
+    parent.appendChild = function <T extends Node>(this: Node, node: T) {
+      if (
+        (node instanceof HTMLScriptElement ||
+          node instanceof HTMLLinkElement) &&
+        !node.nonce
+      ) {
+        node.nonce = nonce
+      }
+      return Node.prototype.appendChild.call(this, node) as T
+    }
+  }
+
+  try {
+    return __turbopack_load__(chunkData)
+  } finally {
+    for (const parent of parents) {
+      // Restores `Node.prototype.appendChild`.
+      delete (parent as Partial<Node>).appendChild
+    }
+  }
+}
@@ -10,6 +10,7 @@ declare global {
 
   var __next_require__: (id: string) => any
   var __next_chunk_load__: (id: string) => Promise
+  var __next_chunk_nonce__: string | undefined
   var __next_f: (
     | [isBootStrap: 0]
     | [isNotBootstrap: 1, responsePartial: string]
@@ -95,6 +95,11 @@ declare module 'CLIENT_MODULE' {
   export const __turbopack_module_id__: string
 }
 
+declare module 'CLIENT_REFERENCE_MODULE' {
+  const defaultExport: unknown
+  export default defaultExport
+}
+
 declare module 'CLIENT_CHUNKS' {
   const moduleId: string
   export default moduleId
@@ -1,8 +1,12 @@
-use anyhow::{bail, Result};
+use anyhow::Result;
+use turbo_tasks::ValueToString;
 use turbopack_binding::{
     turbo::tasks_fs::FileSystemPathVc,
     turbopack::{
-        core::{asset::AssetVc, compile_time_info::CompileTimeInfoVc},
+        core::{
+            asset::{Asset, AssetVc},
+            compile_time_info::CompileTimeInfoVc,
+        },
         ecmascript::chunk::EcmascriptChunkPlaceableVc,
         turbopack::{
             module_options::ModuleOptionsContextVc,
@@ -14,7 +18,7 @@ use turbopack_binding::{
 };
 
 use crate::next_client_component::{
-    with_chunking_context_scope_asset::WithChunkingContextScopeAsset,
+    error::ClientReferenceError, with_chunking_context_scope_asset::WithChunkingContextScopeAsset,
     with_client_chunks::WithClientChunksAsset,
 };
 
@@ -24,6 +28,12 @@ pub struct NextServerComponentTransition {
     pub rsc_module_options_context: ModuleOptionsContextVc,
     pub rsc_resolve_options_context: ResolveOptionsContextVc,
     pub server_root: FileSystemPathVc,
+    /// Server components smaller than this number of bytes don't get their
+    /// own isolated chunk. `None` always isolates server components.
+    pub isolated_chunk_size_threshold: Option<u32>,
+    /// Makes server components usable through `require()`. Off by default to
+    /// keep the regular ESM output small.
+    pub cjs_interop: bool,
 }
 
 #[turbo_tasks::value_impl]
@@ -59,7 +69,10 @@ impl Transition for NextServerComponentTransition {
         _context: ModuleAssetContextVc,
     ) -> Result<AssetVc> {
         let Some(asset) = EcmascriptChunkPlaceableVc::resolve_from(asset).await? else {
-            bail!("Not an ecmascript module");
+            return Err(ClientReferenceError::ServerComponentNotEcmascript(
+                asset.ident().to_string().await?.clone_value(),
+            )
+            .into());
         };
 
         Ok(WithChunkingContextScopeAsset {
@@ -67,6 +80,8 @@ impl Transition for NextServerComponentTransition {
                 asset,
                 // next.js code already adds _next prefix
                 server_root: self.server_root.join("_next"),
+                isolated_chunk_size_threshold: self.isolated_chunk_size_threshold,
+                cjs_interop: self.cjs_interop,
             }
             .cell()
             .into(),
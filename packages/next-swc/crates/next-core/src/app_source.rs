@@ -203,8 +203,29 @@ fn next_ssr_client_module_transition(
     .into()
 }
 
+/// Returns the `experimental.turbo.isolatedChunkSizeThreshold` config.
+async fn isolated_chunk_size_threshold(next_config: NextConfigVc) -> Result<Option<u32>> {
+    Ok(next_config
+        .await?
+        .experimental
+        .turbo
+        .as_ref()
+        .and_then(|turbo| turbo.isolated_chunk_size_threshold))
+}
+
+/// Returns the `experimental.turbo.serverComponentsCjsInterop` config.
+async fn server_components_cjs_interop(next_config: NextConfigVc) -> Result<bool> {
+    Ok(next_config
+        .await?
+        .experimental
+        .turbo
+        .as_ref()
+        .and_then(|turbo| turbo.server_components_cjs_interop)
+        .unwrap_or_default())
+}
+
 #[turbo_tasks::function]
-fn next_server_component_transition(
+async fn next_server_component_transition(
     project_path: FileSystemPathVc,
     execution_context: ExecutionContextVc,
     app_dir: FileSystemPathVc,
@@ -212,7 +233,7 @@ fn next_server_component_transition(
     process_env: ProcessEnvVc,
     next_config: NextConfigVc,
     server_addr: ServerAddrVc,
-) -> TransitionVc {
+) -> Result<TransitionVc> {
     let ty = Value::new(ServerContextType::AppRSC { app_dir });
     let mode = NextMode::Development;
     let rsc_compile_time_info = get_server_compile_time_info(mode, process_env, server_addr);
@@ -221,41 +242,46 @@ fn next_server_component_transition(
     let rsc_module_options_context =
         get_server_module_options_context(project_path, execution_context, ty, mode, next_config);
 
-    NextServerComponentTransition {
+    Ok(NextServerComponentTransition {
         rsc_compile_time_info,
         rsc_module_options_context,
         rsc_resolve_options_context,
         server_root,
+        isolated_chunk_size_threshold: isolated_chunk_size_threshold(next_config).await?,
+        cjs_interop: server_components_cjs_interop(next_config).await?,
     }
     .cell()
-    .into()
+    .into())
 }
 
 #[turbo_tasks::function]
-fn next_edge_server_component_transition(
+async fn next_edge_server_component_transition(
     project_path: FileSystemPathVc,
     execution_context: ExecutionContextVc,
     app_dir: FileSystemPathVc,
     server_root: FileSystemPathVc,
     next_config: NextConfigVc,
     server_addr: ServerAddrVc,
-) -> TransitionVc {
+) -> Result<TransitionVc> {
     let ty = Value::new(ServerContextType::AppRSC { app_dir });
     let mode = NextMode::Development;
-    let rsc_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let rsc_compile_time_info =
+        get_edge_compile_time_info(mode, project_path, Some(server_addr), None);
     let rsc_resolve_options_context =
-        get_edge_resolve_options_context(project_path, ty, next_config, execution_context);
+        get_edge_resolve_options_context(project_path, ty, mode, next_config, execution_context);
     let rsc_module_options_context =
         get_server_module_options_context(project_path, execution_context, ty, mode, next_config);
 
-    NextServerComponentTransition {
+    Ok(NextServerComponentTransition {
         rsc_compile_time_info,
         rsc_module_options_context,
         rsc_resolve_options_context,
         server_root,
+        isolated_chunk_size_threshold: isolated_chunk_size_threshold(next_config).await?,
+        cjs_interop: server_components_cjs_interop(next_config).await?,
     }
     .cell()
-    .into()
+    .into())
 }
 
 #[turbo_tasks::function]
@@ -270,7 +296,8 @@ fn next_edge_route_transition(
 ) -> TransitionVc {
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(NextMode::Development, project_path, Some(server_addr), None);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -281,8 +308,13 @@ fn next_edge_route_transition(
     )
     .reference_chunk_source_maps(should_debug("app_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_path, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_path,
+        server_ty,
+        NextMode::Development,
+        next_config,
+        execution_context,
+    );
 
     NextEdgeRouteTransition {
         edge_compile_time_info,
@@ -310,7 +342,8 @@ fn next_edge_page_transition(
 ) -> TransitionVc {
     let server_ty = Value::new(ServerContextType::AppRoute { app_dir });
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(NextMode::Development, project_path, Some(server_addr), None);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -322,8 +355,13 @@ fn next_edge_page_transition(
     .layer("ssr")
     .reference_chunk_source_maps(should_debug("app_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_path, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_path,
+        server_ty,
+        NextMode::Development,
+        next_config,
+        execution_context,
+    );
 
     NextEdgePageTransition {
         edge_compile_time_info,
@@ -351,8 +389,9 @@ fn app_context(
     server_addr: ServerAddrVc,
     output_path: FileSystemPathVc,
 ) -> ModuleAssetContextVc {
-    let next_server_to_client_transition = NextServerToClientTransition { ssr }.cell().into();
     let mode = NextMode::Development;
+    let next_server_to_client_transition =
+        NextServerToClientTransition::new(ssr, mode).cell().into();
 
     let mut transitions = HashMap::new();
     transitions.insert(
@@ -1049,6 +1088,7 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
                 template,
                 not_found,
                 metadata,
+                global_error: _,
                 route: _,
             } = &*components.await?;
             write_component(state, "page", *page)?;
@@ -1066,7 +1106,7 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
         walk_tree(&mut state, loader_tree).await?;
 
         let State {
-            inner_assets,
+            mut inner_assets,
             imports,
             loader_tree_code,
             unsupported_metadata,
@@ -1083,13 +1123,41 @@ import {}, {{ chunks as {} }} from "COMPONENT_{}";
             .emit();
         }
 
-        let mut result = RopeBuilder::from(indoc! {"
+        // A global-error file replaces the root layout, so it is wrapped through
+        // the server component transition like the other components, which
+        // pulls in the client runtime for it.
+        let global_error = loader_tree.await?.components.await?.global_error;
+        if let Some(global_error) = global_error {
+            inner_assets.insert(
+                "GLOBAL_ERROR".to_string(),
+                context.with_transition(rsc_transition).process(
+                    SourceAssetVc::new(global_error).into(),
+                    Value::new(ReferenceType::EcmaScriptModules(
+                        EcmaScriptModulesReferenceSubType::Undefined,
+                    )),
+                ),
+            );
+        }
+
+        let mut result = RopeBuilder::from(if global_error.is_some() {
+            indoc! {"
+                import GlobalError from \"GLOBAL_ERROR\";
+            "}
+        } else {
+            indoc! {"
                 \"TURBOPACK { chunking-type: isolatedParallel; transition: next-edge-server-component }\";
                 import GlobalErrorMod from \"next/dist/client/components/error-boundary\"
                 const { GlobalError } = GlobalErrorMod;
+            "}
+        });
+        write!(
+            result,
+            "{}",
+            indoc! {"
                 \"TURBOPACK { chunking-type: isolatedParallel; transition: next-edge-server-component }\";
                 import base from \"next/dist/server/app-render/entry-base\"\n
-            "});
+            "}
+        )?;
 
         for import in imports {
             writeln!(result, "{import}")?;
@@ -37,6 +37,10 @@ pub struct Components {
     pub template: Option<FileSystemPathVc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub not_found: Option<FileSystemPathVc>,
+    /// Replaces the root layout when it throws, so only the one in the app
+    /// directory is used.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub global_error: Option<FileSystemPathVc>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<FileSystemPathVc>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -54,6 +58,7 @@ impl Components {
             loading: self.loading,
             template: self.template,
             not_found: self.not_found,
+            global_error: self.global_error,
             default: None,
             route: None,
             metadata: self.metadata.clone(),
@@ -68,6 +73,7 @@ impl Components {
             loading: a.loading.or(b.loading),
             template: a.template.or(b.template),
             not_found: a.not_found.or(b.not_found),
+            global_error: a.global_error.or(b.global_error),
             default: a.default.or(b.default),
             route: a.route.or(b.route),
             metadata: Metadata::merge(&a.metadata, &b.metadata),
@@ -326,6 +332,7 @@ async fn get_directory_tree(
                             "loading" => components.loading = Some(file),
                             "template" => components.template = Some(file),
                             "not-found" => components.not_found = Some(file),
+                            "global-error" => components.global_error = Some(file),
                             "default" => components.default = Some(file),
                             "route" => components.route = Some(file),
                             "manifest" => {
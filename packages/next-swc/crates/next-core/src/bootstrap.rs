@@ -137,7 +137,7 @@ pub async fn bootstrap(
 }
 
 /// This normalizes an app page to a pathname.
-fn normalize_app_page_to_pathname(page: &str) -> String {
+pub(crate) fn normalize_app_page_to_pathname(page: &str) -> String {
     // Split the page string by '/' and collect it into a Vec<&str>.
     let segments: Vec<&str> = page.split('/').collect();
     let segment_count = segments.len();
@@ -214,6 +214,15 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_ignore_nested_and_root_groups() {
+        assert_eq!(
+            normalize_app_page_to_pathname("(marketing)/(landing)/about/page"),
+            "/about"
+        );
+        assert_eq!(normalize_app_page_to_pathname("(marketing)/page"), "");
+    }
+
     #[test]
     fn test_ignore_parallel_segments() {
         assert_eq!(
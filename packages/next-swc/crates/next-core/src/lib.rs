@@ -5,7 +5,7 @@
 #![feature(str_split_remainder)]
 
 mod app_render;
-mod app_segment_config;
+pub mod app_segment_config;
 mod app_source;
 pub mod app_structure;
 mod babel;
@@ -20,7 +20,7 @@ pub mod next_client;
 pub mod next_client_chunks;
 mod next_client_component;
 pub mod next_config;
-mod next_edge;
+pub mod next_edge;
 mod next_font;
 pub mod next_image;
 mod next_import_map;
@@ -43,7 +43,7 @@ pub use app_source::create_app_source;
 pub use page_loader::create_page_loader_entry_asset;
 pub use page_source::create_page_source;
 pub use turbopack_binding::{turbopack::node::source_map, *};
-pub use util::{pathname_for_path, PathType};
+pub use util::{parse_config_from_source, pathname_for_path, NextRuntime, PathType};
 pub use web_entry_source::create_web_entry_source;
 
 pub fn register() {
@@ -342,6 +342,19 @@ pub async fn get_client_runtime_entries(
 ) -> Result<RuntimeEntriesVc> {
     let mut runtime_entries = vec![];
 
+    // This has to run before anything can load a chunk.
+    if *next_config.chunk_nonce().await? {
+        runtime_entries.push(
+            RuntimeEntry::Request(
+                RequestVc::parse(Value::new(Pattern::Constant(
+                    "./build/client/nonce.ts".to_string(),
+                ))),
+                next_js_fs().root().join("_"),
+            )
+            .cell(),
+        );
+    }
+
     if matches!(
         *ty,
         ClientContextType::App { .. } | ClientContextType::Pages { .. },
@@ -8,6 +8,7 @@ use crate::{
     next_shared::transforms::{
         get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
         get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_next_strip_directives_rule,
     },
 };
 
@@ -33,9 +34,11 @@ pub async fn get_next_client_transforms_rules(
             );
             Some(pages_dir)
         }
-        ClientContextType::App { .. } | ClientContextType::Fallback | ClientContextType::Other => {
+        ClientContextType::App { .. } => {
+            rules.push(get_next_strip_directives_rule());
             None
         }
+        ClientContextType::Fallback | ClientContextType::Other => None,
     };
 
     rules.push(get_next_dynamic_transform_rule(true, false, false, pages_dir).await?);
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use turbo_tasks::Value;
+use turbo_tasks::{primitives::StringVc, Value};
 use turbopack_binding::{
     turbo::tasks_fs::FileSystemPathVc,
     turbopack::{
@@ -15,7 +15,7 @@ use turbopack_binding::{
     },
 };
 
-use super::with_chunks::WithChunksAsset;
+use super::with_chunks::{modifier, WithChunksAsset};
 use crate::{
     mode::NextMode,
     next_client::context::{
@@ -32,6 +32,7 @@ pub struct NextClientChunksTransition {
     pub client_resolve_options_context: ResolveOptionsContextVc,
     pub client_chunking_context: ChunkingContextVc,
     pub server_root: FileSystemPathVc,
+    pub ident_modifier: StringVc,
 }
 
 #[turbo_tasks::value_impl]
@@ -72,6 +73,7 @@ impl NextClientChunksTransitionVc {
             ),
             client_compile_time_info,
             server_root,
+            ident_modifier: modifier(ty),
         }
         .cell()
     }
@@ -114,6 +116,7 @@ impl Transition for NextClientChunksTransition {
                 WithChunksAsset {
                     asset: placeable,
                     chunking_context: self.client_chunking_context,
+                    ident_modifier: self.ident_modifier,
                 }
                 .cell()
                 .into()
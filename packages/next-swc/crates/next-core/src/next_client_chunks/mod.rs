@@ -1,5 +1,8 @@
 pub(crate) mod client_chunks_transition;
 pub(crate) mod in_chunking_context_asset;
+pub(crate) mod visit_dynamic;
 pub(crate) mod with_chunks;
 
 pub use client_chunks_transition::NextClientChunksTransitionVc;
+pub use visit_dynamic::{visit_dynamic, NextDynamicEntriesVc};
+pub use with_chunks::{WithChunksAsset, WithChunksAssetVc};
@@ -0,0 +1,75 @@
+use anyhow::Result;
+use turbo_tasks::{
+    graph::{AdjacencyMap, GraphTraversal},
+    TryJoinIterExt,
+};
+use turbopack_binding::turbopack::core::{
+    asset::{Asset, AssetVc},
+    ident::AssetIdentVc,
+    reference::AssetReference,
+};
+
+use super::with_chunks::WithChunksAssetVc;
+
+/// A list of `next/dynamic` entries.
+#[turbo_tasks::value(transparent)]
+pub struct NextDynamicEntries(Vec<WithChunksAssetVc>);
+
+impl NextDynamicEntriesVc {
+    /// Returns each entry together with the ident of its client entry module,
+    /// e.g. for logging or to cross-reference entries with a manifest.
+    pub async fn with_client_entry_idents(self) -> Result<Vec<(AssetIdentVc, WithChunksAssetVc)>> {
+        Ok(self
+            .await?
+            .iter()
+            .map(|&entry| (entry.entry_asset().ident(), entry))
+            .collect())
+    }
+}
+
+/// Walks the module graph starting at `entry` and collects all `next/dynamic`
+/// entries that are reachable from it.
+///
+/// The walk does not descend into the dynamic entries themselves, as their
+/// module graph belongs to the client.
+#[turbo_tasks::function]
+pub async fn visit_dynamic(entry: AssetVc) -> Result<NextDynamicEntriesVc> {
+    let assets = AdjacencyMap::new()
+        .skip_duplicates()
+        .visit([entry], get_referenced_assets)
+        .await
+        .completed()?
+        .into_inner()
+        .into_reverse_topological()
+        .collect::<Vec<_>>();
+
+    let dynamic_entries = assets
+        .into_iter()
+        .map(|asset| async move { WithChunksAssetVc::resolve_from(asset).await })
+        .try_join()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect();
+
+    Ok(NextDynamicEntriesVc::cell(dynamic_entries))
+}
+
+async fn get_referenced_assets(asset: AssetVc) -> Result<impl Iterator<Item = AssetVc> + Send> {
+    let references = if WithChunksAssetVc::resolve_from(asset).await?.is_some() {
+        vec![]
+    } else {
+        asset.references().await?.clone_value()
+    };
+
+    Ok(references
+        .into_iter()
+        .map(|reference| async move {
+            let primary_assets = reference.resolve_reference().primary_assets().await?;
+            Ok(primary_assets.clone_value())
+        })
+        .try_join()
+        .await?
+        .into_iter()
+        .flatten())
+}
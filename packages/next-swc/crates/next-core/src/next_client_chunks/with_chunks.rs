@@ -5,7 +5,8 @@ use indoc::writedoc;
 use turbopack_binding::{
     turbo::{
         tasks::{primitives::StringVc, TryJoinIterExt, Value},
-        tasks_fs::rope::RopeBuilder,
+        tasks_fs::{rope::RopeBuilder, FileContent},
+        tasks_hash::hash_xxh3_hash64,
     },
     turbopack::{
         core::{
@@ -16,7 +17,7 @@ use turbopack_binding::{
                 ChunkingContextVc, ChunksDataVc,
             },
             ident::AssetIdentVc,
-            reference::AssetReferencesVc,
+            reference::{AssetReferencesVc, SingleAssetReferenceVc},
         },
         ecmascript::{
             chunk::{
@@ -30,22 +31,41 @@ use turbopack_binding::{
     },
 };
 
+use crate::next_client::context::ClientContextType;
+
+/// Returns the ident modifier of the entries created for the client context
+/// `ty`. Each router gets its own modifier, so that the entries of the app and
+/// the pages router can be told apart in traces.
 #[turbo_tasks::function]
-fn modifier() -> StringVc {
-    StringVc::cell("chunks".to_string())
+pub fn modifier(ty: Value<ClientContextType>) -> StringVc {
+    StringVc::cell(
+        match ty.into_value() {
+            ClientContextType::Pages { .. } => "chunks (pages)",
+            ClientContextType::App { .. } => "chunks (app)",
+            ClientContextType::Fallback | ClientContextType::Other => "chunks",
+        }
+        .to_string(),
+    )
+}
+
+#[turbo_tasks::function]
+fn wrapped_asset_reference_description() -> StringVc {
+    StringVc::cell("client chunks entry".to_string())
 }
 
 #[turbo_tasks::value(shared)]
 pub struct WithChunksAsset {
     pub asset: EcmascriptChunkPlaceableVc,
     pub chunking_context: ChunkingContextVc,
+    /// The modifier added to the ident of `asset`, see [modifier].
+    pub ident_modifier: StringVc,
 }
 
 #[turbo_tasks::value_impl]
 impl Asset for WithChunksAsset {
     #[turbo_tasks::function]
     fn ident(&self) -> AssetIdentVc {
-        self.asset.ident().with_modifier(modifier())
+        self.asset.ident().with_modifier(self.ident_modifier)
     }
 
     #[turbo_tasks::function]
@@ -58,11 +78,14 @@ impl Asset for WithChunksAsset {
         let this = self_vc.await?;
         let entry_chunk = self_vc.entry_chunk();
 
-        Ok(AssetReferencesVc::cell(vec![ChunkGroupReferenceVc::new(
-            this.chunking_context,
-            entry_chunk,
-        )
-        .into()]))
+        Ok(AssetReferencesVc::cell(vec![
+            ChunkGroupReferenceVc::new(this.chunking_context, entry_chunk).into(),
+            // Not a chunkable reference, so it doesn't change how the entry is
+            // chunked. It only makes the wrapped asset reachable when walking
+            // the asset graph.
+            SingleAssetReferenceVc::new(this.asset.into(), wrapped_asset_reference_description())
+                .into(),
+        ]))
     }
 }
 
@@ -113,8 +136,36 @@ impl WithChunksAssetVc {
         Ok(this.asset.as_root_chunk(this.chunking_context))
     }
 
+    /// Returns the client entry module wrapped by this asset. Unlike
+    /// [`WithChunksAssetVc::chunks`], this doesn't chunk anything, so it can
+    /// be used to inspect the entry's exports or references on their own.
+    #[turbo_tasks::function]
+    pub async fn entry_asset(self) -> Result<EcmascriptChunkPlaceableVc> {
+        Ok(self.await?.asset)
+    }
+
+    /// Returns an id for the chunk group of this entry that only depends on
+    /// the entry module's path within the project and its source, so that it's
+    /// the same across builds and machines. The path tells apart modules with
+    /// the same source, and the source makes the id change with the module.
+    #[turbo_tasks::function]
+    pub async fn chunk_group_id(self) -> Result<StringVc> {
+        let this = self.await?;
+        let path = &this.asset.ident().path().await?.path;
+        let hash = match &*this.asset.content().file_content().await? {
+            FileContent::Content(file) => hash_xxh3_hash64(&format!(
+                "{path}:{:016x}",
+                hash_xxh3_hash64(file.content().to_bytes()?)
+            )),
+            // Virtual entries have no source, their path is enough.
+            FileContent::NotFound => hash_xxh3_hash64(path),
+        };
+        Ok(StringVc::cell(format!("{hash:016x}")))
+    }
+
+    /// Returns the client chunks loaded by this entry.
     #[turbo_tasks::function]
-    async fn chunks(self) -> Result<AssetsVc> {
+    pub async fn chunks(self) -> Result<AssetsVc> {
         let this = self.await?;
         Ok(this.chunking_context.chunk_group(self.entry_chunk()))
     }
@@ -0,0 +1,20 @@
+use thiserror::Error;
+
+/// Failures to turn a module into one side of a client/server component
+/// boundary. Every variant carries the ident of the offending module, so
+/// callers can categorize these with `anyhow::Error::downcast_ref` instead of
+/// matching on messages.
+#[derive(Debug, Error)]
+pub enum ClientReferenceError {
+    #[error("{0} is not an ecmascript module, so it can't be used as a server component")]
+    ServerComponentNotEcmascript(String),
+    #[error("{0} is not chunk placeable, so it can't be used as a client component")]
+    NotChunkPlaceable(String),
+    #[error("{0} doesn't have ESM or CommonJS exports, so it can't be used as a client component")]
+    UnsupportedExportType(String),
+    #[error(
+        "{0} uses `export *`, which isn't supported in client components. Export the names \
+         explicitly instead"
+    )]
+    StarExport(String),
+}
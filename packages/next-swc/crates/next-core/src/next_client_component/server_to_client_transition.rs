@@ -1,11 +1,13 @@
 use anyhow::Result;
 use indexmap::indexmap;
 use turbo_tasks::Value;
+use turbo_tasks_fs::FileContent;
 use turbopack_binding::turbopack::{
     core::{
-        asset::AssetVc,
+        asset::{Asset, AssetVc},
         context::AssetContext,
         reference_type::{EntryReferenceSubType, InnerAssetsVc, ReferenceType},
+        source_asset::SourceAssetVc,
     },
     turbopack::{
         transition::{Transition, TransitionVc},
@@ -13,11 +15,23 @@ use turbopack_binding::turbopack::{
     },
 };
 
-use crate::embed_js::next_asset;
+use crate::{embed_js::next_asset, mode::NextMode};
 
 #[turbo_tasks::value(shared)]
 pub struct NextServerToClientTransition {
     pub ssr: bool,
+    /// Registers the exports of client modules for fast refresh in their client
+    /// chunks. Only enabled in development.
+    pub fast_refresh: bool,
+}
+
+impl NextServerToClientTransition {
+    pub fn new(ssr: bool, mode: NextMode) -> Self {
+        Self {
+            ssr,
+            fast_refresh: matches!(mode, NextMode::Development),
+        }
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -27,20 +41,54 @@ impl Transition for NextServerToClientTransition {
         self_vc: NextServerToClientTransitionVc,
         asset: AssetVc,
         context: ModuleAssetContextVc,
-        _reference_type: Value<ReferenceType>,
+        reference_type: Value<ReferenceType>,
     ) -> Result<AssetVc> {
-        let internal_asset = next_asset(if self_vc.await?.ssr {
+        let context = self_vc.process_context(context);
+
+        // Only modules marked with "use client" are client boundaries. Anything
+        // else, like a server-only module imported through this transition by
+        // mistake, stays a regular server module instead of becoming a client
+        // reference.
+        if let FileContent::Content(file) = &*asset.content().file_content().await? {
+            if !has_use_client_directive(&file.content().to_str()?) {
+                return Ok(context.process(asset, reference_type));
+            }
+        }
+
+        // Symlinked copies of a client module would otherwise each get their own
+        // client reference and chunks. Building the reference from the real path
+        // lets them share one.
+        let path = asset.ident().path().resolve().await?;
+        let real_path = path.realpath().resolve().await?;
+        let asset = if real_path != path {
+            SourceAssetVc::new(real_path).into()
+        } else {
+            asset
+        };
+
+        let this = self_vc.await?;
+        let internal_asset = next_asset(if this.ssr {
             "entry/app/server-to-client-ssr.tsx"
         } else {
             "entry/app/server-to-client.tsx"
         });
-        let context = self_vc.process_context(context);
-        let client_chunks = context.with_transition("next-client-chunks").process(
-            asset,
-            Value::new(ReferenceType::Entry(
-                EntryReferenceSubType::AppClientComponent,
-            )),
-        );
+        // In development, the client chunk registers the exports of the client
+        // module for fast refresh, and re-exports them.
+        let client_chunks = if this.fast_refresh {
+            context.with_transition("next-client-chunks").process(
+                next_asset("entry/app/client-reference-refresh.ts"),
+                Value::new(ReferenceType::Internal(InnerAssetsVc::cell(indexmap! {
+                    "CLIENT_REFERENCE_MODULE".to_string() => asset,
+                }))),
+            )
+        } else {
+            context.with_transition("next-client-chunks").process(
+                asset,
+                Value::new(ReferenceType::Entry(
+                    EntryReferenceSubType::AppClientComponent,
+                )),
+            )
+        };
         let client_module = context.with_transition("next-ssr-client-module").process(
             asset,
             Value::new(ReferenceType::Entry(
@@ -56,3 +104,49 @@ impl Transition for NextServerToClientTransition {
         ))
     }
 }
+
+/// Whether the directive prologue of `source` contains `"use client"`.
+fn has_use_client_directive(source: &str) -> bool {
+    let mut rest = source.strip_prefix("#!").map_or(source, |rest| {
+        rest.split_once('\n').map_or("", |(_, rest)| rest)
+    });
+    loop {
+        rest = rest.trim_start();
+        if let Some(comment) = rest.strip_prefix("//") {
+            rest = comment.split_once('\n').map_or("", |(_, rest)| rest);
+        } else if let Some(comment) = rest.strip_prefix("/*") {
+            let Some((_, after)) = comment.split_once("*/") else {
+                return false;
+            };
+            rest = after;
+        } else if let Some(quote @ ('\'' | '"')) = rest.chars().next() {
+            let Some(end) = rest[1..].find(quote) else {
+                return false;
+            };
+            if &rest[1..end + 1] == "use client" {
+                return true;
+            }
+            rest = rest[end + 2..].trim_start_matches([' ', '\t']);
+            rest = rest.strip_prefix(';').unwrap_or(rest);
+        } else {
+            return false;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::has_use_client_directive;
+
+    #[test]
+    fn directive_prologue() {
+        assert!(has_use_client_directive("'use client'\nexport default 1"));
+        assert!(has_use_client_directive(
+            "#!/usr/bin/env node\n// comment\n/* block */\n\"use strict\";\n\"use client\";"
+        ));
+        assert!(!has_use_client_directive("export default 1\n'use client'"));
+        assert!(!has_use_client_directive("import 'use client'"));
+        assert!(!has_use_client_directive("'use server'"));
+        assert!(!has_use_client_directive(""));
+    }
+}
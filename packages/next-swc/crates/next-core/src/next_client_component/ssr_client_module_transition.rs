@@ -1,8 +1,14 @@
 use anyhow::Result;
+use turbo_tasks::ValueToString;
 use turbopack_binding::turbopack::{
-    core::{asset::AssetVc, compile_time_info::CompileTimeInfoVc},
+    core::{
+        asset::{Asset, AssetVc},
+        compile_time_info::CompileTimeInfoVc,
+    },
     turbopack::{
-        ecmascript::chunk::EcmascriptChunkPlaceableVc,
+        ecmascript::chunk::{
+            EcmascriptChunkPlaceable, EcmascriptChunkPlaceableVc, EcmascriptExports,
+        },
         module_options::ModuleOptionsContextVc,
         resolve_options_context::ResolveOptionsContextVc,
         transition::{Transition, TransitionVc},
@@ -10,7 +16,9 @@ use turbopack_binding::turbopack::{
     },
 };
 
-use super::with_chunking_context_scope_asset::WithChunkingContextScopeAsset;
+use super::{
+    error::ClientReferenceError, with_chunking_context_scope_asset::WithChunkingContextScopeAsset,
+};
 
 #[turbo_tasks::value(shared)]
 pub struct NextSSRClientModuleTransition {
@@ -51,17 +59,28 @@ impl Transition for NextSSRClientModuleTransition {
         asset: AssetVc,
         _context: ModuleAssetContextVc,
     ) -> Result<AssetVc> {
-        Ok(
-            if let Some(placeable) = EcmascriptChunkPlaceableVc::resolve_from(asset).await? {
-                WithChunkingContextScopeAsset {
-                    asset: placeable,
-                    layer: "ssr".to_string(),
+        let ident = asset.ident().to_string().await?.clone_value();
+        let Some(placeable) = EcmascriptChunkPlaceableVc::resolve_from(asset).await? else {
+            return Err(ClientReferenceError::NotChunkPlaceable(ident).into());
+        };
+
+        // The client reference proxy exposes the exports of the client module by
+        // name, which star exports and non-module values don't have.
+        match &*placeable.get_exports().await? {
+            EcmascriptExports::EsmExports(exports) => {
+                if !exports.await?.star_exports.is_empty() {
+                    return Err(ClientReferenceError::StarExport(ident).into());
                 }
-                .cell()
-                .into()
-            } else {
-                asset
-            },
-        )
+            }
+            EcmascriptExports::CommonJs | EcmascriptExports::None => {}
+            _ => return Err(ClientReferenceError::UnsupportedExportType(ident).into()),
+        }
+
+        Ok(WithChunkingContextScopeAsset {
+            asset: placeable,
+            layer: "ssr".to_string(),
+        }
+        .cell()
+        .into())
     }
 }
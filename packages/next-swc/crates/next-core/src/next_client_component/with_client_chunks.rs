@@ -1,8 +1,13 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashSet},
+    fmt::Write,
+};
+
 use anyhow::{Context, Result};
 use indoc::formatdoc;
 use turbo_tasks::{primitives::StringVc, TryJoinIterExt, Value, ValueToString, ValueToStringVc};
 use turbopack_binding::{
-    turbo::tasks_fs::FileSystemPathVc,
+    turbo::tasks_fs::{FileContent, FileSystemPathVc},
     turbopack::{
         core::{
             asset::{Asset, AssetContentVc, AssetVc, AssetsVc},
@@ -23,24 +28,75 @@ use turbopack_binding::{
         turbopack::ecmascript::{
             chunk::{
                 EcmascriptChunkItem, EcmascriptChunkItemContent, EcmascriptChunkItemContentVc,
-                EcmascriptChunkItemVc, EcmascriptChunkPlaceable, EcmascriptChunkPlaceableVc,
-                EcmascriptChunkVc, EcmascriptChunkingContextVc, EcmascriptExports,
-                EcmascriptExportsVc,
+                EcmascriptChunkItemOptions, EcmascriptChunkItemVc, EcmascriptChunkPlaceable,
+                EcmascriptChunkPlaceableVc, EcmascriptChunkVc, EcmascriptChunkingContextVc,
+                EcmascriptExports, EcmascriptExportsVc,
             },
+            references::esm::{EsmExport, EsmExports, EsmExportsVc},
             utils::StringifyJs,
         },
     },
 };
 
+use crate::util::parse_experimental_ppr_from_source;
+
 #[turbo_tasks::function]
 fn modifier() -> StringVc {
     StringVc::cell("client chunks".to_string())
 }
 
+/// How the segment of a server component is prerendered.
+#[turbo_tasks::value(shared)]
+#[derive(Debug, Clone, Copy)]
+pub enum PrerenderStrategy {
+    /// The segment is either fully prerendered or fully rendered on demand.
+    Full,
+    /// The static shell of the segment is prerendered and dynamic boundaries
+    /// are streamed in on demand. Opted into with
+    /// `export const experimental_ppr = true`.
+    Partial,
+}
+
 #[turbo_tasks::value(shared)]
 pub struct WithClientChunksAsset {
     pub asset: EcmascriptChunkPlaceableVc,
     pub server_root: FileSystemPathVc,
+    /// Server components whose source is smaller than this number of bytes
+    /// are placed in their parent's chunk group instead of getting their own
+    /// isolated chunk.
+    pub isolated_chunk_size_threshold: Option<u32>,
+    /// Also makes the module usable through `require()`: the `default` export
+    /// resolves to the server component itself instead of its module
+    /// namespace, and the exports are flagged with `__esModule`.
+    pub cjs_interop: bool,
+}
+
+impl WithClientChunksAsset {
+    /// The reference from this wrapper to the server component it wraps.
+    fn wrapped_reference(&self) -> AssetReferenceVc {
+        WithClientChunksAssetReference {
+            asset: self.asset.into(),
+            isolated_chunk_size_threshold: self.isolated_chunk_size_threshold,
+        }
+        .cell()
+        .into()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl WithClientChunksAssetVc {
+    /// Returns the prerender strategy the server component's segment config
+    /// asks for.
+    #[turbo_tasks::function]
+    pub async fn prerender_strategy(self) -> Result<PrerenderStrategyVc> {
+        let this = self.await?;
+        let strategy = if *parse_experimental_ppr_from_source(this.asset.into()).await? {
+            PrerenderStrategy::Partial
+        } else {
+            PrerenderStrategy::Full
+        };
+        Ok(strategy.cell())
+    }
 }
 
 #[turbo_tasks::value_impl]
@@ -57,11 +113,7 @@ impl Asset for WithClientChunksAsset {
 
     #[turbo_tasks::function]
     fn references(&self) -> AssetReferencesVc {
-        AssetReferencesVc::cell(vec![WithClientChunksAssetReference {
-            asset: self.asset.into(),
-        }
-        .cell()
-        .into()])
+        AssetReferencesVc::cell(vec![self.wrapped_reference()])
     }
 }
 
@@ -103,9 +155,34 @@ impl EcmascriptChunkPlaceable for WithClientChunksAsset {
     }
 
     #[turbo_tasks::function]
-    fn get_exports(&self) -> EcmascriptExportsVc {
-        // TODO This should be EsmExports
-        EcmascriptExports::Value.cell()
+    async fn get_exports(&self) -> Result<EcmascriptExportsVc> {
+        let mut exports = BTreeMap::new();
+        for name in ["default", "chunks"] {
+            exports.insert(name.to_string(), EsmExport::LocalBinding(name.to_string()));
+        }
+        let mut star_exports = Vec::new();
+        if let EcmascriptExports::EsmExports(inner) = &*self.asset.get_exports().await? {
+            let inner = inner.await?;
+            let reference = self.wrapped_reference();
+            for name in inner.exports.keys() {
+                if name == "default" || name == "chunks" {
+                    continue;
+                }
+                exports.insert(
+                    name.clone(),
+                    EsmExport::ImportedBinding(reference, name.clone()),
+                );
+            }
+            star_exports = inner.star_exports.clone();
+        }
+        Ok(EcmascriptExports::EsmExports(
+            EsmExports {
+                exports,
+                star_exports,
+            }
+            .cell(),
+        )
+        .cell())
     }
 }
 
@@ -117,31 +194,18 @@ struct WithClientChunksChunkItem {
 
 #[turbo_tasks::value_impl]
 impl WithClientChunksChunkItemVc {
-    #[turbo_tasks::function]
-    async fn chunks(self) -> Result<AssetsVc> {
-        let this = self.await?;
-        let inner = this.inner.await?;
-        Ok(this
-            .context
-            .chunk_group(inner.asset.as_root_chunk(this.context.into())))
-    }
-
     #[turbo_tasks::function]
     async fn client_chunks(self) -> Result<AssetsVc> {
         let this = self.await?;
         let inner = this.inner.await?;
-        let chunks = self.chunks();
         let output_root = this.context.output_root().await?;
 
+        // Only expose CSS chunks as client chunks.
         let mut client_chunks = Vec::new();
-        for chunk in &*chunks.await? {
-            let extension = chunk.ident().path().extension().await?;
-            // Only expose CSS chunks as client chunks.
-            if &*extension == "css" {
-                if let Some(path) = output_root.get_path_to(&*chunk.ident().path().await?) {
-                    client_chunks
-                        .push(ProxiedAssetVc::new(*chunk, inner.server_root.join(path)).into());
-                }
+        for chunk in &*client_css_chunks(this.context, inner.asset).await? {
+            if let Some(path) = output_root.get_path_to(&*chunk.ident().path().await?) {
+                client_chunks
+                    .push(ProxiedAssetVc::new(*chunk, inner.server_root.join(path)).into());
             }
         }
 
@@ -159,6 +223,66 @@ impl WithClientChunksChunkItemVc {
     }
 }
 
+/// Collects the names a module exports through `export * from`, following
+/// star exports of star exported modules. Modules which aren't ESM can't be
+/// expanded statically and are skipped.
+async fn star_export_names(exports: EsmExportsVc) -> Result<BTreeSet<String>> {
+    let mut names = BTreeSet::new();
+    let mut visited = HashSet::new();
+    let mut queue = exports.await?.star_exports.clone();
+    while let Some(reference) = queue.pop() {
+        for &asset in &*reference.resolve_reference().primary_assets().await? {
+            if !visited.insert(asset) {
+                continue;
+            }
+            let Some(placeable) = EcmascriptChunkPlaceableVc::resolve_from(asset).await? else {
+                continue;
+            };
+            if let EcmascriptExports::EsmExports(exports) = &*placeable.get_exports().await? {
+                let exports = exports.await?;
+                // `export *` never re-exports the default export.
+                names.extend(
+                    exports
+                        .exports
+                        .keys()
+                        .filter(|name| *name != "default")
+                        .cloned(),
+                );
+                queue.extend(exports.star_exports.iter().copied());
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Computes the chunk group of a client asset. This is keyed by the asset
+/// rather than by the chunk item, so that the same client component reached
+/// through different references (e.g. re-exported from several barrel files)
+/// is only chunked once.
+#[turbo_tasks::function]
+fn client_chunk_group(
+    context: EcmascriptChunkingContextVc,
+    asset: EcmascriptChunkPlaceableVc,
+) -> AssetsVc {
+    context.chunk_group(asset.as_root_chunk(context.into()))
+}
+
+/// Returns the CSS chunks in the chunk group of a client asset.
+#[turbo_tasks::function]
+async fn client_css_chunks(
+    context: EcmascriptChunkingContextVc,
+    asset: EcmascriptChunkPlaceableVc,
+) -> Result<AssetsVc> {
+    let mut css_chunks = Vec::new();
+    for chunk in &*client_chunk_group(context, asset).await? {
+        let extension = chunk.ident().path().extension().await?;
+        if &*extension == "css" {
+            css_chunks.push(*chunk);
+        }
+    }
+    Ok(AssetsVc::cell(css_chunks))
+}
+
 #[turbo_tasks::value_impl]
 impl EcmascriptChunkItem for WithClientChunksChunkItem {
     #[turbo_tasks::function]
@@ -179,21 +303,62 @@ impl EcmascriptChunkItem for WithClientChunksChunkItem {
             .collect();
 
         let module_id = inner.asset.as_chunk_item(this.context).id().await?;
+
+        let (default_export, interop) = if inner.cjs_interop {
+            (
+                format!("__turbopack_import__({}).default", StringifyJs(&module_id)),
+                "Object.defineProperty(exports, \"__esModule\", { value: true });\n",
+            )
+        } else {
+            (
+                format!("__turbopack_import__({})", StringifyJs(&module_id)),
+                "",
+            )
+        };
+
+        // Forward the named exports of the server component (e.g. segment config like
+        // `revalidate` or `dynamic`) so they survive this wrapper at runtime. Names
+        // coming from `export * from` are expanded, as the wrapper only defines the
+        // bindings it lists.
+        let mut named_exports = String::new();
+        if let EcmascriptExports::EsmExports(exports) = &*inner.asset.get_exports().await? {
+            let mut names: BTreeSet<String> = exports.await?.exports.keys().cloned().collect();
+            names.extend(star_export_names(*exports).await?);
+            for name in &names {
+                if name == "default" || name == "chunks" {
+                    continue;
+                }
+                writeln!(
+                    named_exports,
+                    "    {}: () => __turbopack_import__({})[{}],",
+                    StringifyJs(name),
+                    StringifyJs(&module_id),
+                    StringifyJs(name),
+                )?;
+            }
+        }
+
         Ok(EcmascriptChunkItemContent {
             inner_code: formatdoc!(
                 // We store the chunks in a binding, otherwise a new array would be created every
                 // time the export binding is read.
                 r#"
                     __turbopack_esm__({{
-                        default: () => __turbopack_import__({}),
+                        default: () => {},
                         chunks: () => chunks,
-                    }});
-                    const chunks = {:#};
+                    {}}});
+                    {}const chunks = {:#};
                 "#,
-                StringifyJs(&module_id),
+                default_export,
+                named_exports,
+                interop,
                 StringifyJs(&chunks_data),
             )
             .into(),
+            options: EcmascriptChunkItemOptions {
+                exports: inner.cjs_interop,
+                ..Default::default()
+            },
             ..Default::default()
         }
         .cell())
@@ -211,14 +376,7 @@ impl ChunkItem for WithClientChunksChunkItem {
     async fn references(self_vc: WithClientChunksChunkItemVc) -> Result<AssetReferencesVc> {
         let this = self_vc.await?;
         let inner = this.inner.await?;
-        let mut references = Vec::new();
-        references.push(
-            WithClientChunksAssetReference {
-                asset: inner.asset.into(),
-            }
-            .cell()
-            .into(),
-        );
+        let mut references = vec![inner.wrapped_reference()];
         let client_chunks = self_vc.client_chunks();
         let client_chunks = client_chunks.await?;
         let client_chunk = StringVc::cell("client chunk".to_string());
@@ -235,6 +393,7 @@ impl ChunkItem for WithClientChunksChunkItem {
 #[turbo_tasks::value]
 struct WithClientChunksAssetReference {
     asset: AssetVc,
+    isolated_chunk_size_threshold: Option<u32>,
 }
 
 #[turbo_tasks::value_impl]
@@ -259,7 +418,16 @@ impl AssetReference for WithClientChunksAssetReference {
 #[turbo_tasks::value_impl]
 impl ChunkableAssetReference for WithClientChunksAssetReference {
     #[turbo_tasks::function]
-    fn chunking_type(&self) -> ChunkingTypeOptionVc {
-        ChunkingTypeOptionVc::cell(Some(ChunkingType::IsolatedParallel))
+    async fn chunking_type(&self) -> Result<ChunkingTypeOptionVc> {
+        if let Some(threshold) = self.isolated_chunk_size_threshold {
+            if let FileContent::Content(file) = &*self.asset.content().file_content().await? {
+                if file.content().len() < threshold as usize {
+                    return Ok(ChunkingTypeOptionVc::cell(Some(ChunkingType::Parallel)));
+                }
+            }
+        }
+        Ok(ChunkingTypeOptionVc::cell(Some(
+            ChunkingType::IsolatedParallel,
+        )))
     }
 }
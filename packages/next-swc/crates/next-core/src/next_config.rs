@@ -3,7 +3,7 @@ use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
 use serde_json::Value as JsonValue;
 use turbo_tasks::{
-    primitives::{BoolVc, JsonValueVc, StringVc, StringsVc},
+    primitives::{BoolVc, JsonValueVc, StringVc, StringsVc, U64Vc},
     trace::TraceRawVcs,
     CompletionVc, Value,
 };
@@ -45,6 +45,9 @@ use turbopack_binding::{
 
 use crate::{embed_js::next_asset, next_shared::transforms::ModularizeImportPackageConfig};
 
+/// The default of `experimental.serverActionsBodySizeLimit`, 1 MB.
+const DEFAULT_SERVER_ACTIONS_BODY_SIZE_LIMIT: u64 = 1024 * 1024;
+
 #[turbo_tasks::value(serialization = "custom", eq = "manual")]
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -66,13 +69,13 @@ pub struct NextConfig {
     pub compiler: Option<CompilerConfig>,
 
     pub output: Option<OutputType>,
+    pub base_path: String,
 
     // unsupported
     cross_origin: Option<String>,
     amp: AmpConfig,
     analytics_id: String,
     asset_prefix: String,
-    base_path: String,
     clean_dist_dir: bool,
     compress: bool,
     dev_indicators: DevIndicatorsConfig,
@@ -350,6 +353,48 @@ pub enum RemotePatternProtocal {
     Https,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct SubResourceIntegrity {
+    pub algorithm: Option<String>,
+}
+
+/// A size, either in bytes or as a string with a unit, like `"2mb"`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(untagged)]
+pub enum SizeLimit {
+    Bytes(f64),
+    WithUnit(String),
+}
+
+impl SizeLimit {
+    /// Returns the size in bytes. Units are powers of 1024, like in the
+    /// `bytes` package Next.js parses them with.
+    pub fn to_bytes(&self) -> Option<u64> {
+        match self {
+            SizeLimit::Bytes(bytes) => (*bytes >= 0.0).then_some(*bytes as u64),
+            SizeLimit::WithUnit(size) => {
+                let size = size.trim().to_ascii_lowercase();
+                let unit_start = size
+                    .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+                    .unwrap_or(size.len());
+                let (value, unit) = size.split_at(unit_start);
+                let value: f64 = value.parse().ok()?;
+                let exponent = match unit.trim() {
+                    "" | "b" => 0,
+                    "kb" => 1,
+                    "mb" => 2,
+                    "gb" => 3,
+                    "tb" => 4,
+                    "pb" => 5,
+                    _ => return None,
+                };
+                Some((value * 1024f64.powi(exponent)) as u64)
+            }
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize, TraceRawVcs)]
 #[serde(rename_all = "camelCase")]
 pub struct ExperimentalTurboConfig {
@@ -357,6 +402,15 @@ pub struct ExperimentalTurboConfig {
     pub loaders: Option<JsonValue>,
     pub rules: Option<IndexMap<String, RuleConfigItem>>,
     pub resolve_alias: Option<IndexMap<String, JsonValue>>,
+    /// Additional conditions to use when resolving `exports` in the edge
+    /// runtime.
+    pub edge_custom_conditions: Option<Vec<String>>,
+    /// Server components whose source is smaller than this number of bytes
+    /// share their parent's chunk group instead of getting an isolated one.
+    pub isolated_chunk_size_threshold: Option<u32>,
+    /// Makes server components usable through `require()`, by also exporting
+    /// them as CommonJS.
+    pub server_components_cjs_interop: Option<bool>,
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, TraceRawVcs)]
@@ -388,6 +442,12 @@ pub struct ExperimentalConfig {
     pub isr_memory_cache_size: Option<f64>,
     pub isr_flush_to_disk: Option<bool>,
     mdx_rs: Option<bool>,
+    pub sri: Option<SubResourceIntegrity>,
+    /// Adds the page's CSP nonce to the tags the client chunk loader inserts.
+    chunk_nonce: Option<bool>,
+    pub server_actions: Option<bool>,
+    pub server_actions_body_size_limit: Option<SizeLimit>,
+    pub server_actions_allowed_origins: Option<Vec<String>>,
 
     // unsupported
     adjust_font_fallbacks: Option<bool>,
@@ -426,7 +486,6 @@ pub struct ExperimentalConfig {
     shared_pool: Option<bool>,
     skip_middleware_url_normalize: Option<bool>,
     skip_trailing_slash_redirect: Option<bool>,
-    sri: Option<serde_json::Value>,
     swc_file_reading: Option<bool>,
     swc_minify: Option<bool>,
     swc_minify_debug_options: Option<serde_json::Value>,
@@ -545,6 +604,21 @@ impl NextConfigVc {
         Ok(StringsVc::cell(self.await?.page_extensions.clone()))
     }
 
+    #[turbo_tasks::function]
+    pub async fn swc_minify(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(self.await?.swc_minify))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn base_path(self) -> Result<StringVc> {
+        Ok(StringVc::cell(self.await?.base_path.clone()))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn asset_prefix(self) -> Result<StringVc> {
+        Ok(StringVc::cell(self.await?.asset_prefix.clone()))
+    }
+
     #[turbo_tasks::function]
     pub async fn rewrites(self) -> Result<RewritesVc> {
         Ok(self.await?.rewrites.clone().cell())
@@ -621,12 +695,72 @@ impl NextConfigVc {
     }
 
     #[turbo_tasks::function]
-    pub async fn mdx_rs(self) -> Result<BoolVc> {
+    pub async fn edge_custom_conditions(self) -> Result<StringsVc> {
+        Ok(StringsVc::cell(
+            self.await?
+                .experimental
+                .turbo
+                .as_ref()
+                .and_then(|t| t.edge_custom_conditions.clone())
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn enable_server_actions(self) -> Result<BoolVc> {
+        Ok(BoolVc::cell(
+            self.await?.experimental.server_actions.unwrap_or_default(),
+        ))
+    }
+
+    /// Returns the maximum size of a server action request body, in bytes.
+    #[turbo_tasks::function]
+    pub async fn server_actions_body_size_limit(self) -> Result<U64Vc> {
+        let this = self.await?;
+        let limit = match &this.experimental.server_actions_body_size_limit {
+            Some(limit) => limit.to_bytes().with_context(|| {
+                format!("invalid experimental.serverActionsBodySizeLimit: {limit:?}")
+            })?,
+            None => DEFAULT_SERVER_ACTIONS_BODY_SIZE_LIMIT,
+        };
+        Ok(U64Vc::cell(limit))
+    }
+
+    /// Returns the origins, besides the app's own, that server actions can be
+    /// called from.
+    #[turbo_tasks::function]
+    pub async fn server_actions_allowed_origins(self) -> Result<StringsVc> {
+        Ok(StringsVc::cell(
+            self.await?
+                .experimental
+                .server_actions_allowed_origins
+                .clone()
+                .unwrap_or_default(),
+        ))
+    }
+
+    #[turbo_tasks::function]
+    pub async fn chunk_nonce(self) -> Result<BoolVc> {
         Ok(BoolVc::cell(
-            self.await?.experimental.mdx_rs.unwrap_or(false),
+            self.await?.experimental.chunk_nonce.unwrap_or_default(),
         ))
     }
 
+    #[turbo_tasks::function]
+    pub async fn mdx_rs(self) -> Result<BoolVc> {
+        let this = self.await?;
+        // `.mdx` pages only produce ECMAScript through the MDX transform, so
+        // listing `mdx` in `pageExtensions` enables it unless it was explicitly
+        // turned off.
+        Ok(BoolVc::cell(this.experimental.mdx_rs.unwrap_or_else(
+            || {
+                this.page_extensions
+                    .iter()
+                    .any(|extension| extension == "mdx")
+            },
+        )))
+    }
+
     #[turbo_tasks::function]
     pub async fn sass_config(self) -> Result<JsonValueVc> {
         Ok(JsonValueVc::cell(
@@ -779,3 +913,47 @@ impl Issue for OutdatedConfigIssue {
         StringVc::cell(self.description.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{ExperimentalConfig, SizeLimit};
+
+    #[test]
+    fn size_limit_to_bytes() {
+        assert_eq!(SizeLimit::Bytes(500.0).to_bytes(), Some(500));
+        assert_eq!(
+            SizeLimit::WithUnit("2mb".to_string()).to_bytes(),
+            Some(2 * 1024 * 1024)
+        );
+        assert_eq!(
+            SizeLimit::WithUnit("1.5 KB".to_string()).to_bytes(),
+            Some(1536)
+        );
+        assert_eq!(SizeLimit::WithUnit("100".to_string()).to_bytes(), Some(100));
+        assert_eq!(
+            SizeLimit::WithUnit("2 parsecs".to_string()).to_bytes(),
+            None
+        );
+        assert_eq!(SizeLimit::Bytes(-1.0).to_bytes(), None);
+    }
+
+    #[test]
+    fn server_actions_options() {
+        let experimental: ExperimentalConfig = serde_json::from_value(serde_json::json!({
+            "serverActions": true,
+            "serverActionsBodySizeLimit": "2mb",
+            "serverActionsAllowedOrigins": ["proxy.example.com"],
+        }))
+        .unwrap();
+
+        assert_eq!(experimental.server_actions, Some(true));
+        assert_eq!(
+            experimental.server_actions_body_size_limit,
+            Some(SizeLimit::WithUnit("2mb".to_string()))
+        );
+        assert_eq!(
+            experimental.server_actions_allowed_origins,
+            Some(vec!["proxy.example.com".to_string()])
+        );
+    }
+}
@@ -1,5 +1,5 @@
 use anyhow::Result;
-use turbo_tasks::Value;
+use turbo_tasks::{primitives::BoolVc, Value};
 use turbopack_binding::{
     turbo::tasks_fs::FileSystemPathVc,
     turbopack::{
@@ -15,20 +15,29 @@ use turbopack_binding::{
             free_var_references,
         },
         node::execution_context::ExecutionContextVc,
-        turbopack::resolve_options_context::{ResolveOptionsContext, ResolveOptionsContextVc},
+        turbopack::{
+            module_options::{ModuleOptionsContext, ModuleOptionsContextVc},
+            resolve_options_context::{ResolveOptionsContext, ResolveOptionsContextVc},
+        },
     },
 };
 
 use crate::{
-    next_config::NextConfigVc, next_import_map::get_next_edge_import_map,
-    next_server::context::ServerContextType,
-    next_shared::resolve::UnsupportedModulesResolvePluginVc, util::foreign_code_context_condition,
+    mode::NextMode,
+    next_config::NextConfigVc,
+    next_edge::wasm::get_edge_wasm_rule,
+    next_import_map::get_next_edge_import_map,
+    next_server::context::{
+        get_server_module_options_context, ServerContextType, REACT_SERVER_CONDITION,
+    },
+    next_shared::resolve::UnsupportedModulesResolvePluginVc,
+    util::foreign_code_context_condition,
 };
 
-fn defines() -> CompileTimeDefines {
+fn defines(mode: NextMode) -> CompileTimeDefines {
     compile_time_defines!(
         process.turbopack = true,
-        process.env.NODE_ENV = "development",
+        process.env.NODE_ENV = mode.node_env(),
         process.env.__NEXT_CLIENT_ROUTER_FILTER_ENABLED = false,
         process.env.NEXT_RUNTIME = "edge"
     )
@@ -37,59 +46,140 @@ fn defines() -> CompileTimeDefines {
 }
 
 #[turbo_tasks::function]
-fn next_edge_defines() -> CompileTimeDefinesVc {
-    defines().cell()
+fn next_edge_defines(mode: NextMode) -> CompileTimeDefinesVc {
+    defines(mode).cell()
 }
 
+/// Returns the module the `process` free variable resolves to.
+///
+/// The edge runtime provides `process.env`, which production code only reads
+/// through the defines above. Production builds use a polyfill that doesn't
+/// bundle the full `process` implementation as a fallback.
+fn process_polyfill(mode: NextMode) -> &'static str {
+    match mode {
+        NextMode::Development => "next/dist/build/polyfills/process",
+        NextMode::Build => "next/dist/build/polyfills/process-production",
+    }
+}
+
+/// Later entries win, so the built-in free vars below override any of the
+/// same name in `extra_free_vars`.
 #[turbo_tasks::function]
-fn next_edge_free_vars(project_path: FileSystemPathVc) -> FreeVarReferencesVc {
-    free_var_references!(
-        ..defines().into_iter(),
+async fn next_edge_free_vars(
+    mode: NextMode,
+    project_path: FileSystemPathVc,
+    extra_free_vars: Option<FreeVarReferencesVc>,
+) -> Result<FreeVarReferencesVc> {
+    let extra_free_vars = match extra_free_vars {
+        Some(extra_free_vars) => extra_free_vars.await?.clone_value(),
+        None => Default::default(),
+    };
+    Ok(free_var_references!(
+        ..extra_free_vars.into_iter(),
+        ..defines(mode).into_iter(),
         Buffer = FreeVarReference::EcmaScriptModule {
             request: "next/dist/compiled/buffer".to_string(),
             context: Some(project_path),
             export: Some("Buffer".to_string()),
         },
         process = FreeVarReference::EcmaScriptModule {
-            request: "next/dist/build/polyfills/process".to_string(),
+            request: process_polyfill(mode).to_string(),
             context: Some(project_path),
             export: Some("default".to_string()),
         },
     )
-    .cell()
+    .cell())
+}
+
+/// Whether edge chunks should be minified. Edge functions are size limited, so
+/// unlike Node.js server chunks, which stay readable for stack traces, they
+/// are minified in production builds unless `swcMinify` is disabled.
+#[turbo_tasks::function]
+pub async fn should_minify_edge_chunks(
+    mode: NextMode,
+    next_config: NextConfigVc,
+) -> Result<BoolVc> {
+    Ok(BoolVc::cell(
+        matches!(mode, NextMode::Build) && *next_config.swc_minify().await?,
+    ))
 }
 
+/// Returns the compile time info for the edge runtime.
+///
+/// Static edge builds have no server to talk to and pass `None` as
+/// `server_addr`, in which case the environment carries no address at all.
+///
+/// Integrations can provide additional globals for edge code through
+/// `extra_free_vars`, e.g. mapping `Crypto` to a polyfill. They can't
+/// replace `Buffer`, `process` or the defines.
 #[turbo_tasks::function]
 pub fn get_edge_compile_time_info(
+    mode: NextMode,
     project_path: FileSystemPathVc,
-    server_addr: ServerAddrVc,
+    server_addr: Option<ServerAddrVc>,
+    extra_free_vars: Option<FreeVarReferencesVc>,
 ) -> CompileTimeInfoVc {
+    let server_addr = server_addr.unwrap_or_else(ServerAddrVc::empty);
     CompileTimeInfo::builder(EnvironmentVc::new(Value::new(
         ExecutionEnvironment::EdgeWorker(EdgeWorkerEnvironment { server_addr }.into()),
     )))
-    .defines(next_edge_defines())
-    .free_var_references(next_edge_free_vars(project_path))
+    .defines(next_edge_defines(mode))
+    .free_var_references(next_edge_free_vars(mode, project_path, extra_free_vars))
     .cell()
 }
 
+/// Returns the module options context for `ty` code running in the edge
+/// runtime, like edge pages or the middleware.
+///
+/// On top of the server module options, edge code can import `.wasm` files.
+#[turbo_tasks::function]
+pub async fn get_edge_module_options_context(
+    project_path: FileSystemPathVc,
+    execution_context: ExecutionContextVc,
+    ty: Value<ServerContextType>,
+    mode: NextMode,
+    next_config: NextConfigVc,
+) -> Result<ModuleOptionsContextVc> {
+    let module_options_context =
+        get_server_module_options_context(project_path, execution_context, ty, mode, next_config)
+            .await?;
+
+    let mut custom_rules = module_options_context.custom_rules.clone();
+    custom_rules.push(get_edge_wasm_rule());
+
+    Ok(ModuleOptionsContext {
+        custom_rules,
+        ..module_options_context.clone_value()
+    }
+    .cell())
+}
+
 #[turbo_tasks::function]
 pub async fn get_edge_resolve_options_context(
     project_path: FileSystemPathVc,
     ty: Value<ServerContextType>,
+    mode: NextMode,
     next_config: NextConfigVc,
     execution_context: ExecutionContextVc,
 ) -> Result<ResolveOptionsContextVc> {
+    let uses_react_server_condition = ty.uses_react_server_condition();
     let next_edge_import_map =
         get_next_edge_import_map(project_path, ty, next_config, execution_context);
 
+    // https://github.com/vercel/next.js/blob/bf52c254973d99fed9d71507a2e818af80b8ade7/packages/next/src/build/webpack-config.ts#L96-L102
+    let mut custom_conditions = vec![
+        "edge-light".to_string(),
+        "worker".to_string(),
+        mode.node_env().to_string(),
+    ];
+    if uses_react_server_condition {
+        custom_conditions.push(REACT_SERVER_CONDITION.to_string());
+    }
+    custom_conditions.extend(next_config.edge_custom_conditions().await?.iter().cloned());
+
     let resolve_options_context = ResolveOptionsContext {
         enable_node_modules: Some(project_path.root().resolve().await?),
-        // https://github.com/vercel/next.js/blob/bf52c254973d99fed9d71507a2e818af80b8ade7/packages/next/src/build/webpack-config.ts#L96-L102
-        custom_conditions: vec![
-            "edge-light".to_string(),
-            "worker".to_string(),
-            "development".to_string(),
-        ],
+        custom_conditions,
         import_map: Some(next_edge_import_map),
         module: true,
         browser: true,
@@ -0,0 +1,77 @@
+use indexmap::indexmap;
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::core::{asset::AssetVc, chunk::EvaluatableAssetVc, context::AssetContextVc},
+};
+
+use crate::{
+    bootstrap::{route_bootstrap, BootstrapConfigVc},
+    embed_js::next_asset,
+};
+
+/// Wraps the module of an edge function, like the middleware or an edge page,
+/// in the edge bootstrap.
+///
+/// The bootstrap registers the function under [edge_entry_key], which is where
+/// the edge runtime looks up the function called `name` in the middleware
+/// manifest. The page the function handles is the path of `asset` relative to
+/// `base_path`.
+#[turbo_tasks::function]
+pub fn edge_function_entry(
+    asset: AssetVc,
+    context: AssetContextVc,
+    base_path: FileSystemPathVc,
+    name: String,
+) -> EvaluatableAssetVc {
+    route_bootstrap(
+        asset,
+        context,
+        base_path,
+        next_asset("entry/edge-bootstrap.ts"),
+        BootstrapConfigVc::cell(indexmap! {
+            "NAME".to_string() => name,
+        }),
+    )
+}
+
+/// Wraps an app route handler running in the edge runtime in the edge route
+/// bootstrap, which registers it under [edge_entry_key] like
+/// [edge_function_entry] does. `base_path` is the app directory.
+#[turbo_tasks::function]
+pub fn edge_app_route_entry(
+    asset: AssetVc,
+    context: AssetContextVc,
+    base_path: FileSystemPathVc,
+    name: String,
+) -> EvaluatableAssetVc {
+    route_bootstrap(
+        asset,
+        context,
+        base_path,
+        next_asset("entry/app/edge-route-bootstrap.ts"),
+        BootstrapConfigVc::cell(indexmap! {
+            "NAME".to_string() => name,
+        }),
+    )
+}
+
+/// Returns the key of the `_ENTRIES` global the edge bootstrap registers the
+/// edge function called `name` under.
+pub fn edge_entry_key(name: &str) -> String {
+    format!("middleware_{name}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::edge_entry_key;
+
+    #[test]
+    fn matches_edge_bootstrap() {
+        let bootstrap = include_str!("../../js/src/entry/edge-bootstrap.ts");
+        assert!(bootstrap.contains("[`middleware_${NAME}`]"));
+        assert_eq!(
+            edge_entry_key("pages/api/edge"),
+            "middleware_pages/api/edge"
+        );
+    }
+}
@@ -1,3 +1,5 @@
 pub mod context;
+pub mod entry;
 pub mod page_transition;
 pub mod route_transition;
+pub mod wasm;
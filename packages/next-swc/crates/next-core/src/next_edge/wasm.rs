@@ -0,0 +1,92 @@
+use anyhow::Result;
+use indexmap::indexmap;
+use turbopack_binding::{
+    turbo::{
+        tasks::Value,
+        tasks_fs::{File, FileContent},
+        tasks_hash::hash_xxh3_hash64,
+    },
+    turbopack::{
+        core::{
+            asset::{Asset, AssetVc},
+            context::AssetContext,
+            reference_type::{InnerAssetsVc, ReferenceType},
+            resolve::ModulePartVc,
+            virtual_asset::VirtualAssetVc,
+        },
+        ecmascript::utils::StringifyJs,
+        r#static::StaticModuleAssetVc,
+        turbopack::{
+            module_options::{
+                CustomModuleType, CustomModuleTypeVc, ModuleRule, ModuleRuleCondition,
+                ModuleRuleEffect, ModuleType,
+            },
+            ModuleAssetContextVc,
+        },
+    },
+};
+
+/// Returns a rule which handles `.wasm` imports in edge code.
+pub fn get_edge_wasm_rule() -> ModuleRule {
+    ModuleRule::new(
+        ModuleRuleCondition::ResourcePathEndsWith(".wasm".to_string()),
+        vec![ModuleRuleEffect::ModuleType(ModuleType::Custom(
+            EdgeWasmModuleTypeVc::new().into(),
+        ))],
+    )
+}
+
+/// Returns the name of the global binding the edge runtime exposes the
+/// compiled WebAssembly module with.
+pub fn edge_wasm_binding_name(content: &[u8]) -> String {
+    format!("wasm_{:016x}", hash_xxh3_hash64(content))
+}
+
+/// Module type for `.wasm` imports in edge code.
+///
+/// The edge runtime doesn't allow compiling WebAssembly from bytes, so the file
+/// is emitted as a static asset and the runtime provides the compiled
+/// `WebAssembly.Module` through a global binding instead. The bindings are
+/// listed in the `wasm` field of the middleware manifest.
+#[turbo_tasks::value]
+pub struct EdgeWasmModuleType;
+
+#[turbo_tasks::value_impl]
+impl EdgeWasmModuleTypeVc {
+    #[turbo_tasks::function]
+    pub fn new() -> Self {
+        EdgeWasmModuleType.cell()
+    }
+}
+
+#[turbo_tasks::value_impl]
+impl CustomModuleType for EdgeWasmModuleType {
+    #[turbo_tasks::function]
+    async fn create_module(
+        &self,
+        source: AssetVc,
+        context: ModuleAssetContextVc,
+        _part: Option<ModulePartVc>,
+    ) -> Result<AssetVc> {
+        let FileContent::Content(file) = &*source.content().file_content().await? else {
+            return Ok(source);
+        };
+        let binding_name = edge_wasm_binding_name(&file.content().to_bytes()?);
+
+        let static_asset = StaticModuleAssetVc::new(source, context.into());
+        Ok(context.process(
+            VirtualAssetVc::new(
+                source.ident().path().join("wasm-binding.js"),
+                File::from(format!(
+                    "import \"WASM\";\nexport default globalThis[{}];\n",
+                    StringifyJs(&binding_name)
+                ))
+                .into(),
+            )
+            .as_asset(),
+            Value::new(ReferenceType::Internal(InnerAssetsVc::cell(indexmap! {
+                "WASM".to_string() => static_asset.into(),
+            }))),
+        ))
+    }
+}
@@ -3,10 +3,10 @@ use std::collections::{BTreeMap, HashMap};
 use anyhow::{Context, Result};
 use turbo_tasks::Value;
 use turbopack_binding::{
-    turbo::tasks_fs::{glob::GlobVc, FileSystem, FileSystemPathVc},
+    turbo::tasks_fs::{glob::GlobVc, FileJsonContentVc, FileSystem, FileSystemPathVc},
     turbopack::{
         core::{
-            asset::Asset,
+            asset::{Asset, AssetVc},
             resolve::{
                 options::{
                     ConditionValue, ImportMap, ImportMapVc, ImportMapping, ImportMappingVc,
@@ -17,6 +17,7 @@ use turbopack_binding::{
                 resolve, AliasPattern, ResolveAliasMapVc, SubpathValue,
             },
         },
+        ecmascript::typescript::resolve::read_from_tsconfigs,
         node::execution_context::ExecutionContextVc,
         turbopack::{resolve_options, resolve_options_context::ResolveOptionsContext},
     },
@@ -31,6 +32,7 @@ use crate::{
         local::{NextFontLocalCssModuleReplacerVc, NextFontLocalReplacerVc},
     },
     next_server::context::ServerContextType,
+    transform_options::{get_typescript_options, get_typescript_project_references},
 };
 
 // Make sure to not add any external requests here.
@@ -44,6 +46,11 @@ pub async fn get_next_client_import_map(
 ) -> Result<ImportMapVc> {
     let mut import_map = ImportMap::empty();
 
+    // The project's own tsconfig `paths` replace the ones of the projects it
+    // references, so that its imports keep resolving the way it configured them.
+    insert_typescript_project_reference_aliases(&mut import_map, project_path).await?;
+    insert_typescript_path_aliases(&mut import_map, project_path).await?;
+
     insert_next_shared_aliases(
         &mut import_map,
         project_path,
@@ -194,6 +201,11 @@ pub async fn get_next_server_import_map(
 ) -> Result<ImportMapVc> {
     let mut import_map = ImportMap::empty();
 
+    // The project's own tsconfig `paths` replace the ones of the projects it
+    // references, so that its imports keep resolving the way it configured them.
+    insert_typescript_project_reference_aliases(&mut import_map, project_path).await?;
+    insert_typescript_path_aliases(&mut import_map, project_path).await?;
+
     insert_next_shared_aliases(
         &mut import_map,
         project_path,
@@ -238,6 +250,13 @@ pub async fn get_next_server_import_map(
                 "next/dynamic",
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
+            import_map.insert_exact_alias(
+                "private-next-rsc-action-encryption",
+                request_to_import_mapping(
+                    project_path,
+                    "next/dist/build/webpack/loaders/next-flight-loader/action-encryption",
+                ),
+            );
 
             // The sandbox can't be bundled and needs to be external
             import_map.insert_exact_alias("next/dist/server/web/sandbox", external);
@@ -258,6 +277,13 @@ pub async fn get_next_edge_import_map(
 ) -> Result<ImportMapVc> {
     let mut import_map = ImportMap::empty();
 
+    // tsconfig `paths` aliases like `@/` are added explicitly so that edge code
+    // resolves them like server code. They go first, so that the Next.js
+    // aliases below replace any of them mapping the same request. The project's
+    // own aliases replace the ones of the projects it references.
+    insert_typescript_project_reference_aliases(&mut import_map, project_path).await?;
+    insert_typescript_path_aliases(&mut import_map, project_path).await?;
+
     insert_next_shared_aliases(
         &mut import_map,
         project_path,
@@ -291,6 +317,13 @@ pub async fn get_next_edge_import_map(
                 "next/dynamic",
                 request_to_import_mapping(project_path, "next/dist/shared/lib/app-dynamic"),
             );
+            import_map.insert_exact_alias(
+                "private-next-rsc-action-encryption",
+                request_to_import_mapping(
+                    project_path,
+                    "next/dist/build/webpack/loaders/next-flight-loader/action-encryption",
+                ),
+            );
         }
         ServerContextType::Middleware => {}
     }
@@ -559,6 +592,78 @@ pub async fn insert_alias_option<const N: usize>(
     Ok(())
 }
 
+/// Inserts the `compilerOptions.paths` of the project's tsconfig as aliases.
+async fn insert_typescript_path_aliases(
+    import_map: &mut ImportMap,
+    project_path: FileSystemPathVc,
+) -> Result<()> {
+    let Some(tsconfig) = get_typescript_options(project_path).await else {
+        return Ok(());
+    };
+    insert_tsconfig_path_aliases(import_map, &tsconfig).await
+}
+
+/// Inserts the `compilerOptions.paths` of the projects the project's tsconfig
+/// references, so that imports into a composite project resolve the way that
+/// project configured them.
+async fn insert_typescript_project_reference_aliases(
+    import_map: &mut ImportMap,
+    project_path: FileSystemPathVc,
+) -> Result<()> {
+    for tsconfig in get_typescript_project_references(project_path).await? {
+        insert_tsconfig_path_aliases(import_map, &tsconfig).await?;
+    }
+    Ok(())
+}
+
+/// Inserts the `compilerOptions.paths` of `tsconfig` as aliases. Patterns
+/// ending with `*` become wildcard aliases, others exact aliases.
+async fn insert_tsconfig_path_aliases(
+    import_map: &mut ImportMap,
+    tsconfig: &[(FileJsonContentVc, AssetVc)],
+) -> Result<()> {
+    let paths = read_from_tsconfigs(tsconfig, |json, source| {
+        let paths = json["compilerOptions"]["paths"].as_object()?.clone();
+        // Paths are relative to `baseUrl`, or to the tsconfig declaring them.
+        let tsconfig_dir = source.ident().path().parent();
+        let base = match json["compilerOptions"]["baseUrl"].as_str() {
+            Some(base_url) => tsconfig_dir.join(base_url),
+            None => tsconfig_dir,
+        };
+        Some((paths, base))
+    })
+    .await?;
+    let Some((paths, base)) = paths else {
+        return Ok(());
+    };
+
+    for (pattern, targets) in paths {
+        let mappings: Vec<_> = targets
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|target| target.as_str())
+            .map(|target| {
+                let target = target.strip_prefix("./").unwrap_or(target);
+                request_to_import_mapping(base, &format!("./{target}"))
+            })
+            .collect();
+        let mapping = match mappings.len() {
+            0 => continue,
+            1 => mappings[0],
+            _ => ImportMapping::Alternatives(mappings).cell(),
+        };
+        if let Some(prefix) = pattern.strip_suffix('*') {
+            if !prefix.contains('*') {
+                import_map.insert_wildcard_alias(prefix, mapping);
+            }
+        } else if !pattern.contains('*') {
+            import_map.insert_exact_alias(pattern, mapping);
+        }
+    }
+    Ok(())
+}
+
 fn export_value_to_import_mapping(
     value: &SubpathValue,
     conditions: &BTreeMap<String, ConditionValue>,
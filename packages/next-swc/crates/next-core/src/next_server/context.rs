@@ -58,6 +58,9 @@ use crate::{
     util::foreign_code_context_condition,
 };
 
+/// The export condition of React Server Components builds.
+pub const REACT_SERVER_CONDITION: &str = "react-server";
+
 #[turbo_tasks::value(serialization = "auto_for_input")]
 #[derive(Debug, Copy, Clone, Hash, PartialOrd, Ord)]
 pub enum ServerContextType {
@@ -69,6 +72,15 @@ pub enum ServerContextType {
     Middleware,
 }
 
+impl ServerContextType {
+    /// Whether modules are resolved with the `react-server` export condition.
+    /// It selects the server builds of packages like `react` in the React
+    /// Server Components graph, which must not pick up their client builds.
+    pub fn uses_react_server_condition(&self) -> bool {
+        matches!(self, ServerContextType::AppRSC { .. })
+    }
+}
+
 #[turbo_tasks::function]
 pub async fn get_server_resolve_options_context(
     project_path: FileSystemPathVc,
@@ -153,7 +165,7 @@ pub async fn get_server_resolve_options_context(
                 module: true,
                 custom_conditions: vec![
                     mode.node_env().to_string(),
-                    "react-server".to_string(),
+                    REACT_SERVER_CONDITION.to_string(),
                     // TODO
                     "node".to_string(),
                 ],
@@ -0,0 +1,27 @@
+use turbopack_binding::{
+    turbo::tasks_fs::FileSystemPathVc,
+    turbopack::core::{asset::AssetVc, chunk::EvaluatableAssetVc, context::AssetContextVc},
+};
+
+use crate::{
+    bootstrap::{route_bootstrap, BootstrapConfigVc},
+    embed_js::next_asset,
+};
+
+/// Wraps an app route handler in the entry the Next.js server loads route
+/// handlers through, which exports the handler's `routeModule`. `base_path` is
+/// the app directory.
+#[turbo_tasks::function]
+pub fn app_route_entry(
+    asset: AssetVc,
+    context: AssetContextVc,
+    base_path: FileSystemPathVc,
+) -> EvaluatableAssetVc {
+    route_bootstrap(
+        asset,
+        context,
+        base_path,
+        next_asset("build/app/route.ts"),
+        BootstrapConfigVc::empty(),
+    )
+}
@@ -1,4 +1,5 @@
 pub(crate) mod context;
+pub(crate) mod entry;
 pub(crate) mod resolve;
 pub(crate) mod transforms;
 
@@ -6,3 +7,4 @@ pub use context::{
     get_server_compile_time_info, get_server_module_options_context,
     get_server_resolve_options_context, ServerContextType,
 };
+pub use entry::app_route_entry;
@@ -8,6 +8,7 @@ use crate::{
     next_shared::transforms::{
         get_next_dynamic_transform_rule, get_next_font_transform_rule, get_next_image_rule,
         get_next_modularize_imports_rule, get_next_pages_transforms_rule,
+        get_next_strip_directives_rule,
     },
 };
 
@@ -33,9 +34,14 @@ pub async fn get_next_server_transforms_rules(
             );
             (false, Some(pages_dir))
         }
-        ServerContextType::AppSSR { .. } => (false, None),
-        ServerContextType::AppRSC { .. } => (true, None),
-        ServerContextType::AppRoute { .. } => (false, None),
+        ServerContextType::AppSSR { .. } | ServerContextType::AppRoute { .. } => {
+            rules.push(get_next_strip_directives_rule());
+            (false, None)
+        }
+        ServerContextType::AppRSC { .. } => {
+            rules.push(get_next_strip_directives_rule());
+            (true, None)
+        }
         ServerContextType::Middleware { .. } => (false, None),
     };
 
@@ -2,6 +2,7 @@ pub(crate) mod emotion;
 pub(crate) mod modularize_imports;
 pub(crate) mod next_dynamic;
 pub(crate) mod next_font;
+pub(crate) mod next_strip_directives;
 pub(crate) mod next_strip_page_exports;
 pub(crate) mod relay;
 pub(crate) mod styled_components;
@@ -10,6 +11,7 @@ pub(crate) mod styled_jsx;
 pub use modularize_imports::{get_next_modularize_imports_rule, ModularizeImportPackageConfig};
 pub use next_dynamic::get_next_dynamic_transform_rule;
 pub use next_font::get_next_font_transform_rule;
+pub use next_strip_directives::get_next_strip_directives_rule;
 pub use next_strip_page_exports::get_next_pages_transforms_rule;
 pub use relay::get_relay_transform_plugin;
 use turbo_tasks::Value;
@@ -0,0 +1,100 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use swc_core::ecma::ast::{Expr, ExprStmt, Lit, ModuleItem, Program, Stmt, Str};
+use turbopack_binding::turbopack::{
+    ecmascript::{
+        CustomTransformer, EcmascriptInputTransform, EcmascriptInputTransformsVc, TransformContext,
+        TransformPluginVc,
+    },
+    turbopack::module_options::{ModuleRule, ModuleRuleEffect},
+};
+
+use super::module_rule_match_js_no_url;
+
+/// Returns a rule which removes the `"use client"` and `"use server"`
+/// directives from modules. They only mark client and server boundaries, which
+/// are resolved from the source before modules are transformed, so they would
+/// otherwise end up as dead strings in the emitted chunks.
+pub fn get_next_strip_directives_rule() -> ModuleRule {
+    let strip_transform =
+        EcmascriptInputTransform::Plugin(TransformPluginVc::cell(Box::new(NextJsStripDirectives)));
+    ModuleRule::new(
+        module_rule_match_js_no_url(),
+        vec![ModuleRuleEffect::AddEcmascriptTransforms(
+            EcmascriptInputTransformsVc::cell(vec![strip_transform]),
+        )],
+    )
+}
+
+#[derive(Debug)]
+struct NextJsStripDirectives;
+
+#[async_trait]
+impl CustomTransformer for NextJsStripDirectives {
+    async fn transform(&self, program: &mut Program, _ctx: &TransformContext<'_>) -> Result<()> {
+        if let Program::Module(module) = program {
+            strip_boundary_directives(&mut module.body);
+        }
+        Ok(())
+    }
+}
+
+/// Removes the boundary directives from the directive prologue of a module.
+/// Other directives, like `"use strict"`, are kept.
+fn strip_boundary_directives(body: &mut Vec<ModuleItem>) {
+    let prologue_len = body
+        .iter()
+        .take_while(|item| directive(item).is_some())
+        .count();
+    let mut index = 0;
+    body.retain(|item| {
+        let in_prologue = index < prologue_len;
+        index += 1;
+        !(in_prologue && matches!(directive(item), Some("use client" | "use server")))
+    });
+}
+
+fn directive(item: &ModuleItem) -> Option<&str> {
+    match item {
+        ModuleItem::Stmt(Stmt::Expr(ExprStmt { expr, .. })) => match &**expr {
+            Expr::Lit(Lit::Str(Str { value, .. })) => Some(&**value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use swc_core::{
+        common::DUMMY_SP,
+        ecma::ast::{EmptyStmt, Expr, ExprStmt, Lit, ModuleItem, Stmt},
+    };
+
+    use super::{directive, strip_boundary_directives};
+
+    fn directive_item(value: &str) -> ModuleItem {
+        ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(Expr::Lit(Lit::Str(value.into()))),
+        }))
+    }
+
+    #[test]
+    fn strips_boundary_directives_from_prologue() {
+        let mut body = vec![
+            directive_item("use strict"),
+            directive_item("use client"),
+            directive_item("use server"),
+            ModuleItem::Stmt(Stmt::Empty(EmptyStmt { span: DUMMY_SP })),
+            directive_item("use client"),
+        ];
+
+        strip_boundary_directives(&mut body);
+
+        assert_eq!(
+            body.iter().map(directive).collect::<Vec<_>>(),
+            vec![Some("use strict"), None, Some("use client")]
+        );
+    }
+}
@@ -148,7 +148,8 @@ pub async fn create_page_source(
     .cell()
     .into();
 
-    let edge_compile_time_info = get_edge_compile_time_info(project_root, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(mode, project_root, Some(server_addr), None);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_root,
@@ -159,8 +160,13 @@ pub async fn create_page_source(
     )
     .reference_chunk_source_maps(should_debug("page_source"))
     .build();
-    let edge_resolve_options_context =
-        get_edge_resolve_options_context(project_root, server_ty, next_config, execution_context);
+    let edge_resolve_options_context = get_edge_resolve_options_context(
+        project_root,
+        server_ty,
+        mode,
+        next_config,
+        execution_context,
+    );
 
     let next_edge_transition = NextEdgeRouteTransition {
         edge_compile_time_info,
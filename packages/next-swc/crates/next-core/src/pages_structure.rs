@@ -1,5 +1,6 @@
-use anyhow::Result;
-use turbo_tasks::{primitives::StringsVc, CompletionVc};
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use turbo_tasks::{primitives::StringsVc, trace::TraceRawVcs, CompletionVc};
 use turbo_tasks_fs::FileSystemPathOptionVc;
 use turbopack_binding::turbo::tasks_fs::{
     DirectoryContent, DirectoryEntry, FileSystemEntryType, FileSystemPathVc,
@@ -44,6 +45,83 @@ impl PagesStructureItemVc {
         this.next_router_path.await?;
         Ok(CompletionVc::new())
     }
+
+    /// Returns the dynamic parameters of this route, in the order in which
+    /// they appear in `next_router_path`.
+    #[turbo_tasks::function]
+    pub async fn dynamic_params(self) -> Result<DynamicParamsVc> {
+        let this = self.await?;
+        let next_router_path = this.next_router_path.await?;
+        Ok(DynamicParamsVc::cell(dynamic_params_for_path(
+            &next_router_path.path,
+        )?))
+    }
+}
+
+/// A dynamic segment of a route, e.g. `[id]`, `[...slug]` or `[[...slug]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TraceRawVcs)]
+#[serde(rename_all = "camelCase")]
+pub struct DynamicParam {
+    pub name: String,
+    /// Whether this parameter matches multiple segments (`[...name]`).
+    pub catch_all: bool,
+    /// Whether this parameter may match no segment at all (`[[...name]]`).
+    pub optional: bool,
+}
+
+#[turbo_tasks::value(transparent)]
+pub struct DynamicParams(Vec<DynamicParam>);
+
+/// Parses the dynamic parameters out of a Next.js router path.
+pub fn dynamic_params_for_path(path: &str) -> Result<Vec<DynamicParam>> {
+    let mut params = vec![];
+    for segment in path.split('/') {
+        let Some(segment) = segment.strip_prefix('[') else {
+            continue;
+        };
+        let param = if let Some(segment) = segment.strip_prefix("[...") {
+            let Some(name) = segment.strip_suffix("]]") else {
+                bail!(
+                    "path ({}) contains '[[' without matching ']]' at '[[...{}'",
+                    path,
+                    segment
+                );
+            };
+            DynamicParam {
+                name: name.to_string(),
+                catch_all: true,
+                optional: true,
+            }
+        } else if let Some(segment) = segment.strip_prefix("...") {
+            let Some(name) = segment.strip_suffix(']') else {
+                bail!(
+                    "path ({}) contains '[' without matching ']' at '[...{}'",
+                    path,
+                    segment
+                );
+            };
+            DynamicParam {
+                name: name.to_string(),
+                catch_all: true,
+                optional: false,
+            }
+        } else {
+            let Some(name) = segment.strip_suffix(']') else {
+                bail!(
+                    "path ({}) contains '[' without matching ']' at '[{}'",
+                    path,
+                    segment
+                );
+            };
+            DynamicParam {
+                name: name.to_string(),
+                catch_all: false,
+                optional: false,
+            }
+        };
+        params.push(param);
+    }
+    Ok(params)
 }
 
 /// A (sub)directory in the pages directory with all analyzed routes and
@@ -372,13 +450,18 @@ async fn get_pages_structure_for_directory(
     .cell())
 }
 
-fn page_basename<'a>(name: &'a str, page_extensions: &'a [String]) -> Option<&'a str> {
-    if let Some((basename, extension)) = name.rsplit_once('.') {
-        if page_extensions.iter().any(|allowed| allowed == extension) {
-            return Some(basename);
-        }
-    }
-    None
+/// Strips a page extension from a file name. Extensions may contain dots
+/// themselves (e.g. `page.tsx`), in which case the longest matching extension
+/// wins, so that `foo.page.tsx` maps to `foo` rather than `foo.page`.
+fn page_basename<'a>(name: &'a str, page_extensions: &[String]) -> Option<&'a str> {
+    page_extensions
+        .iter()
+        .filter_map(|extension| {
+            name.strip_suffix(extension.as_str())?
+                .strip_suffix('.')
+                .filter(|basename| !basename.is_empty())
+        })
+        .min_by_key(|basename| basename.len())
 }
 
 fn next_router_path_for_basename(
@@ -391,3 +474,59 @@ fn next_router_path_for_basename(
         next_router_path.join(basename)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{dynamic_params_for_path, page_basename, DynamicParam};
+
+    fn param(name: &str, catch_all: bool, optional: bool) -> DynamicParam {
+        DynamicParam {
+            name: name.to_string(),
+            catch_all,
+            optional,
+        }
+    }
+
+    #[test]
+    fn test_static_path() {
+        assert_eq!(dynamic_params_for_path("blog/about").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_dynamic_params() {
+        assert_eq!(
+            dynamic_params_for_path("blog/[id]/[...rest]").unwrap(),
+            vec![param("id", false, false), param("rest", true, false)]
+        );
+    }
+
+    #[test]
+    fn test_optional_catch_all() {
+        assert_eq!(
+            dynamic_params_for_path("docs/[[...slug]]").unwrap(),
+            vec![param("slug", true, true)]
+        );
+    }
+
+    #[test]
+    fn test_unmatched_bracket() {
+        assert!(dynamic_params_for_path("docs/[id").is_err());
+    }
+
+    #[test]
+    fn page_basename_strips_extensions() {
+        let extensions = |extensions: &[&str]| -> Vec<String> {
+            extensions.iter().map(|ext| ext.to_string()).collect()
+        };
+        let default = extensions(&["tsx", "ts", "jsx", "js"]);
+        assert_eq!(page_basename("index.tsx", &default), Some("index"));
+        assert_eq!(page_basename("foo.bar.js", &default), Some("foo.bar"));
+        assert_eq!(page_basename("foo.css", &default), None);
+        assert_eq!(page_basename(".tsx", &default), None);
+
+        let custom = extensions(&["page.tsx", "tsx"]);
+        assert_eq!(page_basename("foo.page.tsx", &custom), Some("foo"));
+        assert_eq!(page_basename("foo.tsx", &custom), Some("foo"));
+        assert_eq!(page_basename("foo.page.ts", &custom), None);
+    }
+}
@@ -46,11 +46,14 @@ use crate::{
     mode::NextMode,
     next_config::NextConfigVc,
     next_edge::{
-        context::{get_edge_compile_time_info, get_edge_resolve_options_context},
+        context::{
+            get_edge_compile_time_info, get_edge_module_options_context,
+            get_edge_resolve_options_context,
+        },
         route_transition::NextEdgeRouteTransition,
     },
     next_import_map::get_next_build_import_map,
-    next_server::context::{get_server_module_options_context, ServerContextType},
+    next_server::context::ServerContextType,
     util::{parse_config_from_source, NextSourceConfigVc},
 };
 
@@ -64,8 +67,10 @@ fn next_configs() -> StringsVc {
     )
 }
 
+/// Returns the paths a middleware file can live at, relative to the project
+/// root.
 #[turbo_tasks::function]
-async fn middleware_files(page_extensions: StringsVc) -> Result<StringsVc> {
+pub async fn middleware_files(page_extensions: StringsVc) -> Result<StringsVc> {
     let extensions = page_extensions.await?;
     let files = ["middleware.", "src/middleware."]
         .into_iter()
@@ -240,7 +245,8 @@ fn edge_transition_map(
     next_config: NextConfigVc,
     execution_context: ExecutionContextVc,
 ) -> TransitionsByNameVc {
-    let edge_compile_time_info = get_edge_compile_time_info(project_path, server_addr);
+    let edge_compile_time_info =
+        get_edge_compile_time_info(NextMode::Development, project_path, Some(server_addr), None);
 
     let edge_chunking_context = DevChunkingContextVc::builder(
         project_path,
@@ -255,11 +261,12 @@ fn edge_transition_map(
     let edge_resolve_options_context = get_edge_resolve_options_context(
         project_path,
         Value::new(ServerContextType::Middleware),
+        NextMode::Development,
         next_config,
         execution_context,
     );
 
-    let server_module_options_context = get_server_module_options_context(
+    let edge_module_options_context = get_edge_module_options_context(
         project_path,
         execution_context,
         Value::new(ServerContextType::Middleware),
@@ -270,7 +277,7 @@ fn edge_transition_map(
     let next_edge_transition = NextEdgeRouteTransition {
         edge_compile_time_info,
         edge_chunking_context,
-        edge_module_options_context: Some(server_module_options_context),
+        edge_module_options_context: Some(edge_module_options_context),
         edge_resolve_options_context,
         output_path: output_path.root(),
         base_path: project_path,
@@ -21,7 +21,7 @@ use turbopack_binding::{
 
 use crate::mode::NextMode;
 
-async fn get_typescript_options(
+pub(crate) async fn get_typescript_options(
     project_path: FileSystemPathVc,
 ) -> Option<Vec<(FileJsonContentVc, AssetVc)>> {
     let tsconfig = find_context_file(project_path, tsconfig());
@@ -39,6 +39,50 @@ async fn get_typescript_options(
     }
 }
 
+/// Returns the tsconfigs of the projects listed in the `references` of the
+/// project's tsconfig, each with the tsconfigs it extends. References to
+/// projects without a readable tsconfig are skipped.
+pub(crate) async fn get_typescript_project_references(
+    project_path: FileSystemPathVc,
+) -> Result<Vec<Vec<(FileJsonContentVc, AssetVc)>>> {
+    let Some(tsconfig) = get_typescript_options(project_path).await else {
+        return Ok(vec![]);
+    };
+    let references = read_from_tsconfigs(&tsconfig, |json, source| {
+        let references = json["references"].as_array()?.clone();
+        Some((references, source.ident().path().parent()))
+    })
+    .await?;
+    let Some((references, tsconfig_dir)) = references else {
+        return Ok(vec![]);
+    };
+
+    let mut result = vec![];
+    for reference in &references {
+        let Some(path) = reference["path"].as_str() else {
+            continue;
+        };
+        // A reference points either at a tsconfig file or at the directory
+        // containing a `tsconfig.json`.
+        let path = tsconfig_dir.join(path);
+        let path = if path.await?.path.ends_with(".json") {
+            path
+        } else {
+            path.join("tsconfig.json")
+        };
+        if let Ok(tsconfig) = read_tsconfigs(
+            path.read(),
+            SourceAssetVc::new(path).into(),
+            node_cjs_resolve_options(path.root()),
+        )
+        .await
+        {
+            result.push(tsconfig);
+        }
+    }
+    Ok(result)
+}
+
 /// Build the transform options for specifically for the typescript's runtime
 /// outputs
 #[turbo_tasks::function]
@@ -1,8 +1,8 @@
 use anyhow::{bail, Context, Result};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use swc_core::ecma::ast::Program;
+use swc_core::ecma::ast::{Module, Program, VarDeclarator};
 use turbo_tasks::{
-    primitives::{JsonValue, JsonValueVc, StringVc},
+    primitives::{BoolVc, JsonValue, JsonValueVc, StringVc},
     trace::TraceRawVcs,
     TaskInput, Value, ValueToString,
 };
@@ -21,7 +21,7 @@ use turbopack_binding::{
             },
         },
         ecmascript::{
-            analyzer::{JsValue, ObjectPart},
+            analyzer::{ConstantNumber, ConstantValue, JsValue, ObjectPart},
             parse::ParseResult,
             EcmascriptModuleAssetVc,
         },
@@ -29,12 +29,19 @@ use turbopack_binding::{
     },
 };
 
-use crate::next_config::{NextConfigVc, OutputType};
+use crate::{
+    bootstrap::normalize_app_page_to_pathname,
+    next_config::{NextConfigVc, OutputType},
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, TaskInput)]
 pub enum PathType {
     Page,
     Data,
+    /// An app router page or route, without its file extension. Route groups,
+    /// parallel route slots and the trailing `page`/`route` segment don't
+    /// appear in the pathname.
+    App,
 }
 
 /// Converts a filename within the server root into a next pathname.
@@ -57,6 +64,10 @@ pub async fn pathname_for_path(
     let path = match (path_ty, path) {
         // "/" is special-cased to "/index" for data routes.
         (PathType::Data, "") => "/index".to_string(),
+        (PathType::App, path) => match normalize_app_page_to_pathname(path) {
+            pathname if pathname.is_empty() => "/".to_string(),
+            pathname => pathname,
+        },
         // `get_path_to` always strips the leading `/` from the path, so we need to add
         // it back here.
         (_, path) => format!("/{}", path),
@@ -110,6 +121,21 @@ pub struct NextSourceConfig {
 
     /// Middleware router matchers
     pub matcher: Option<Vec<String>>,
+
+    /// Regions the page should be deployed to.
+    pub regions: Option<Vec<String>>,
+
+    /// Maximum duration of a single invocation, in seconds.
+    pub max_duration: Option<u32>,
+
+    /// Whether the page is an AMP-first page (`amp: true`). Hybrid AMP pages
+    /// (`amp: "hybrid"`) are served as regular pages too, so they don't set
+    /// this.
+    pub amp: bool,
+
+    /// Whether the page opted out of client-side JavaScript with
+    /// `unstable_runtimeJS: false`.
+    pub disable_runtime_js: bool,
 }
 
 #[turbo_tasks::value_impl]
@@ -173,37 +199,21 @@ pub async fn parse_config_from_source(module_asset: AssetVc) -> Result<NextSourc
             ..
         } = &*ecmascript_asset.parse().await?
         {
-            for item in &module.body {
-                if let Some(decl) = item
-                    .as_module_decl()
-                    .and_then(|mod_decl| mod_decl.as_export_decl())
-                    .and_then(|export_decl| export_decl.decl.as_var())
-                {
-                    for decl in &decl.decls {
-                        if decl
-                            .name
-                            .as_ident()
-                            .map(|ident| &*ident.sym == "config")
-                            .unwrap_or_default()
-                        {
-                            if let Some(init) = decl.init.as_ref() {
-                                let value = eval_context.eval(init);
-                                return Ok(parse_config_from_js_value(module_asset, &value).cell());
-                            } else {
-                                NextSourceConfigParsingIssue {
-                                    ident: module_asset.ident(),
-                                    detail: StringVc::cell(
-                                        "The exported config object must contain an variable \
-                                         initializer."
-                                            .to_string(),
-                                    ),
-                                }
-                                .cell()
-                                .as_issue()
-                                .emit()
-                            }
-                        }
+            if let Some(decl) = find_exported_var(module, "config") {
+                if let Some(init) = decl.init.as_ref() {
+                    let value = eval_context.eval(init);
+                    return Ok(parse_config_from_js_value(module_asset, &value).cell());
+                } else {
+                    NextSourceConfigParsingIssue {
+                        ident: module_asset.ident(),
+                        detail: StringVc::cell(
+                            "The exported config object must contain an variable initializer."
+                                .to_string(),
+                        ),
                     }
+                    .cell()
+                    .as_issue()
+                    .emit()
                 }
             }
         }
@@ -211,6 +221,63 @@ pub async fn parse_config_from_source(module_asset: AssetVc) -> Result<NextSourc
     Ok(NextSourceConfigVc::default())
 }
 
+/// Parses the `experimental_ppr` segment config export of a module, which opts
+/// the segment into partial prerendering. Anything but a literal `true` is
+/// treated as disabled.
+#[turbo_tasks::function]
+pub async fn parse_experimental_ppr_from_source(module_asset: AssetVc) -> Result<BoolVc> {
+    if let Some(ecmascript_asset) = EcmascriptModuleAssetVc::resolve_from(module_asset).await? {
+        if let ParseResult::Ok {
+            program: Program::Module(module),
+            eval_context,
+            ..
+        } = &*ecmascript_asset.parse().await?
+        {
+            if let Some(init) =
+                find_exported_var(module, "experimental_ppr").and_then(|decl| decl.init.as_ref())
+            {
+                match eval_context.eval(init) {
+                    JsValue::Constant(ConstantValue::True) => return Ok(BoolVc::cell(true)),
+                    JsValue::Constant(ConstantValue::False) => {}
+                    value => {
+                        let (explainer, hints) = value.explain(2, 0);
+                        NextSourceConfigParsingIssue {
+                            ident: module_asset.ident(),
+                            detail: StringVc::cell(format!(
+                                "The exported experimental_ppr must be a boolean. Got \
+                                 {explainer}.{hints}"
+                            )),
+                        }
+                        .cell()
+                        .as_issue()
+                        .emit()
+                    }
+                }
+            }
+        }
+    }
+    Ok(BoolVc::cell(false))
+}
+
+/// Finds the declarator of an `export const <name> = ...` statement.
+fn find_exported_var<'a>(module: &'a Module, name: &str) -> Option<&'a VarDeclarator> {
+    module
+        .body
+        .iter()
+        .filter_map(|item| {
+            item.as_module_decl()
+                .and_then(|mod_decl| mod_decl.as_export_decl())
+                .and_then(|export_decl| export_decl.decl.as_var())
+        })
+        .flat_map(|decl| decl.decls.iter())
+        .find(|decl| {
+            decl.name
+                .as_ident()
+                .map(|ident| &*ident.sym == name)
+                .unwrap_or_default()
+        })
+}
+
 fn parse_config_from_js_value(module_asset: AssetVc, value: &JsValue) -> NextSourceConfig {
     let mut config = NextSourceConfig::default();
     let invalid_config = |detail: &str, value: &JsValue| {
@@ -292,6 +359,76 @@ fn parse_config_from_js_value(module_asset: AssetVc, value: &JsValue) -> NextSou
                             }
                             config.matcher = Some(matchers);
                         }
+                        if key == "regions" {
+                            let mut regions = vec![];
+                            match value {
+                                JsValue::Constant(region) => {
+                                    if let Some(region) = region.as_str() {
+                                        regions.push(region.to_string());
+                                    } else {
+                                        invalid_config(
+                                            "The regions property must be a string or array of \
+                                             strings",
+                                            value,
+                                        );
+                                    }
+                                }
+                                JsValue::Array { items, .. } => {
+                                    for item in items {
+                                        if let Some(region) = item.as_str() {
+                                            regions.push(region.to_string());
+                                        } else {
+                                            invalid_config(
+                                                "The regions property must be a string or array \
+                                                 of strings",
+                                                value,
+                                            );
+                                        }
+                                    }
+                                }
+                                _ => invalid_config(
+                                    "The regions property must be a string or array of strings",
+                                    value,
+                                ),
+                            }
+                            config.regions = Some(regions);
+                        }
+                        if key == "maxDuration" {
+                            match value {
+                                JsValue::Constant(ConstantValue::Num(ConstantNumber(seconds)))
+                                    if *seconds >= 0.0 && seconds.fract() == 0.0 =>
+                                {
+                                    config.max_duration = Some(*seconds as u32);
+                                }
+                                _ => invalid_config(
+                                    "The maxDuration property must be a non-negative integer.",
+                                    value,
+                                ),
+                            }
+                        }
+                        if key == "amp" {
+                            match value {
+                                JsValue::Constant(ConstantValue::True) => config.amp = true,
+                                JsValue::Constant(ConstantValue::False) => {}
+                                value if value.as_str() == Some("hybrid") => {}
+                                _ => invalid_config(
+                                    "The amp property must be a boolean or \"hybrid\".",
+                                    value,
+                                ),
+                            }
+                        }
+                        if key == "unstable_runtimeJS" {
+                            match value {
+                                JsValue::Constant(ConstantValue::False) => {
+                                    config.disable_runtime_js = true
+                                }
+                                JsValue::Constant(ConstantValue::True) => {}
+                                _ => invalid_config(
+                                    "The unstable_runtimeJS property must be a boolean.",
+                                    value,
+                                ),
+                            }
+                        }
                     } else {
                         invalid_config(
                             "The exported config object must not contain non-constant strings.",
@@ -370,12 +507,19 @@ pub async fn render_data(
         fetch_cache_key_prefix: Option<String>,
         isr_memory_cache_size: Option<f64>,
         isr_flush_to_disk: Option<bool>,
+        /// In bytes. Only set when server actions are enabled.
+        server_actions_body_size_limit: Option<u64>,
     }
 
     let config = next_config.await?;
     let server_info = ServerInfo::try_from(&*server_addr.await?);
 
     let experimental = &config.experimental;
+    let server_actions_body_size_limit = if *next_config.enable_server_actions().await? {
+        Some(*next_config.server_actions_body_size_limit().await?)
+    } else {
+        None
+    };
 
     let value = serde_json::to_value(Data {
         next_config_output: config.output.clone(),
@@ -384,6 +528,7 @@ pub async fn render_data(
         fetch_cache_key_prefix: experimental.fetch_cache_key_prefix.clone(),
         isr_memory_cache_size: experimental.isr_memory_cache_size,
         isr_flush_to_disk: experimental.isr_flush_to_disk,
+        server_actions_body_size_limit,
     })?;
     Ok(JsonValue(value).cell())
 }
@@ -0,0 +1,12 @@
+import Test from './test'
+
+export const revalidate = 0
+
+export default function Page() {
+  return (
+    <div>
+      <p id="page">rendered</p>
+      <Test />
+    </div>
+  )
+}
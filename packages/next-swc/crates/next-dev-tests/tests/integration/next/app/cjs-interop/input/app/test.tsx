@@ -0,0 +1,11 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should render the default export of a server component with CommonJS interop', () => {
+      expect(document.getElementById('page').textContent).toBe('rendered')
+    })
+  })
+}
@@ -0,0 +1,11 @@
+import Test from './test'
+
+export * from './segment'
+
+export default function Page() {
+  return (
+    <div>
+      <Test />
+    </div>
+  )
+}
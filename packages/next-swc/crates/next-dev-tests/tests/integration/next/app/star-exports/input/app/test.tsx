@@ -0,0 +1,11 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should forward exports of the page module from export *', () => {
+      expect(document.title).toBe('Star exported metadata')
+    })
+  })
+}
@@ -0,0 +1,11 @@
+import Counter from './counter'
+import Test from './test'
+
+export default function Page() {
+  return (
+    <div>
+      <Counter />
+      <Test />
+    </div>
+  )
+}
@@ -0,0 +1,26 @@
+'use client'
+
+import { useTestHarness } from '@turbo/pack-test-harness'
+
+// Matches a boundary directive left as a statement of its own, which is how it
+// would appear in a chunk if it wasn't stripped.
+const DIRECTIVE = /^\s*['"]use (client|server)['"];?\s*$/m
+
+export default function Test() {
+  useTestHarness(() => {
+    it('should strip boundary directives from the emitted chunks', async () => {
+      const chunks = new Set(
+        performance
+          .getEntriesByType('resource')
+          .map((entry) => entry.name)
+          .filter((name) => new URL(name).pathname.endsWith('.js'))
+      )
+      expect(chunks.size).toBeGreaterThan(0)
+
+      for (const chunk of chunks) {
+        const res = await fetch(chunk)
+        expect(await res.text()).not.toMatch(DIRECTIVE)
+      }
+    })
+  })
+}
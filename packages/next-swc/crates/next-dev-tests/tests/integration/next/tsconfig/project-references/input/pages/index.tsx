@@ -0,0 +1,23 @@
+import { useTestHarness } from '@turbo/pack-test-harness'
+import { greeting } from '@shared/greeting'
+import { value } from '@/lib/value'
+
+export default function Page() {
+  useTestHarness(runTests)
+
+  return (
+    <div id="test">
+      {greeting} {value}
+    </div>
+  )
+}
+
+function runTests() {
+  it('should resolve imports with the paths of a referenced project', () => {
+    expect(greeting).toBe('Hello World')
+  })
+
+  it("should resolve the project's own paths over the referenced project's", () => {
+    expect(value).toBe('root')
+  })
+}
@@ -0,0 +1,7 @@
+// The edge runtime provides `process.env`, which is all production edge code
+// is expected to use. Unlike `./process`, this doesn't bundle the full
+// `process` polyfill as a fallback.
+module.exports =
+  global.process?.env && typeof global.process.env === 'object'
+    ? global.process
+    : { env: {} }
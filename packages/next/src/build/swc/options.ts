@@ -162,6 +162,9 @@ function getBaseSWCOptions({
           // TODO-APP: When Server Actions is stable, we need to remove this flag.
           enabled: !!isServerActionsEnabled,
           isServer: !!isServerLayer,
+          // Closed-over values of inline actions are always encrypted before
+          // they are sent to the client.
+          encryption: !!isServerActionsEnabled,
         }
       : undefined,
   }
@@ -0,0 +1,120 @@
+// The values a server action closes over are sent to the client as its bound
+// args. They're serialized with the React flight protocol and encrypted with
+// AES-GCM, so that the client can neither read nor tamper with them. The
+// action id is part of the encrypted payload, which ties the bound args to the
+// action they were created for.
+
+import { streamToString } from '../../../../server/stream-utils/node-web-streams-helper'
+
+// When the app runs on more than one instance, they all need to share the key,
+// which can be passed in as a base64 encoded 256 bit key. Otherwise each
+// process creates its own.
+const ENCRYPTION_KEY_ENV = 'NEXT_SERVER_ACTIONS_ENCRYPTION_KEY'
+const ENCRYPTION_KEY_GLOBAL = Symbol.for('next.server.action-encryption-key')
+
+const textEncoder = new TextEncoder()
+const textDecoder = new TextDecoder()
+
+function getActionEncryptionKey(): Promise<CryptoKey> {
+  const globalForKey = globalThis as unknown as {
+    [ENCRYPTION_KEY_GLOBAL]?: Promise<CryptoKey>
+  }
+  if (!globalForKey[ENCRYPTION_KEY_GLOBAL]) {
+    const rawKey = process.env[ENCRYPTION_KEY_ENV]
+    globalForKey[ENCRYPTION_KEY_GLOBAL] = rawKey
+      ? crypto.subtle.importKey(
+          'raw',
+          stringToUint8Array(atob(rawKey)),
+          'AES-GCM',
+          false,
+          ['encrypt', 'decrypt']
+        )
+      : crypto.subtle.generateKey({ name: 'AES-GCM', length: 256 }, false, [
+          'encrypt',
+          'decrypt',
+        ])
+  }
+  return globalForKey[ENCRYPTION_KEY_GLOBAL]!
+}
+
+function arrayBufferToString(buffer: ArrayBuffer): string {
+  const bytes = new Uint8Array(buffer)
+  let binary = ''
+  for (let i = 0; i < bytes.byteLength; i++) {
+    binary += String.fromCharCode(bytes[i])
+  }
+  return binary
+}
+
+function stringToUint8Array(binary: string): Uint8Array {
+  const bytes = new Uint8Array(binary.length)
+  for (let i = 0; i < binary.length; i++) {
+    bytes[i] = binary.charCodeAt(i)
+  }
+  return bytes
+}
+
+async function encodeActionBoundArg(actionId: string, arg: string) {
+  const key = await getActionEncryptionKey()
+  const iv = crypto.getRandomValues(new Uint8Array(16))
+  const encrypted = await crypto.subtle.encrypt(
+    { name: 'AES-GCM', iv },
+    key,
+    textEncoder.encode(actionId + arg)
+  )
+  return btoa(arrayBufferToString(iv.buffer) + arrayBufferToString(encrypted))
+}
+
+async function decodeActionBoundArg(actionId: string, arg: string) {
+  const key = await getActionEncryptionKey()
+  const payload = atob(arg)
+  let decrypted: string
+  try {
+    decrypted = textDecoder.decode(
+      await crypto.subtle.decrypt(
+        { name: 'AES-GCM', iv: stringToUint8Array(payload.slice(0, 16)) },
+        key,
+        stringToUint8Array(payload.slice(16))
+      )
+    )
+  } catch {
+    throw new Error('Invalid Server Action payload: failed to decrypt.')
+  }
+  if (!decrypted.startsWith(actionId)) {
+    throw new Error('Invalid Server Action payload: failed to decrypt.')
+  }
+  return decrypted.slice(actionId.length)
+}
+
+export async function encryptActionBoundArgs(actionId: string, args: any[]) {
+  const {
+    renderToReadableStream,
+    // eslint-disable-next-line import/no-extraneous-dependencies
+  } = require('react-server-dom-webpack/server.edge')
+
+  // Client references can't be bound, so there is no client manifest to
+  // serialize them with.
+  const serialized = await streamToString(renderToReadableStream(args, {}))
+  return encodeActionBoundArg(actionId, serialized)
+}
+
+export async function decryptActionBoundArgs(
+  actionId: string,
+  encrypted: Promise<string>
+) {
+  const {
+    createFromReadableStream,
+    // eslint-disable-next-line import/no-extraneous-dependencies
+  } = require('react-server-dom-webpack/client.edge')
+
+  const decrypted = await decodeActionBoundArg(actionId, await encrypted)
+  return createFromReadableStream(
+    new ReadableStream({
+      start(controller) {
+        controller.enqueue(textEncoder.encode(decrypted))
+        controller.close()
+      },
+    }),
+    { moduleMap: {} }
+  )
+}
@@ -252,6 +252,9 @@ const configSchema = {
           },
           type: 'object',
         },
+        chunkNonce: {
+          type: 'boolean',
+        },
         clientRouterFilter: {
           type: 'boolean',
         },
@@ -314,6 +317,12 @@ const configSchema = {
             },
           ] as any,
         },
+        serverActionsAllowedOrigins: {
+          type: 'array',
+          items: {
+            type: 'string',
+          },
+        },
         extensionAlias: {
           type: 'object',
         },
@@ -471,6 +480,19 @@ const configSchema = {
             resolveAlias: {
               type: 'object',
             },
+            edgeCustomConditions: {
+              type: 'array',
+              items: {
+                type: 'string',
+              },
+            },
+            isolatedChunkSizeThreshold: {
+              type: 'number',
+              minimum: 0,
+            },
+            serverComponentsCjsInterop: {
+              type: 'boolean',
+            },
           },
         },
         instrumentationHook: {
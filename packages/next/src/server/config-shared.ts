@@ -110,6 +110,21 @@ interface ExperimentalTurboOptions {
    * @see [Turbopack Loaders](https://nextjs.org/docs/app/api-reference/next-config-js/turbo#webpack-loaders)
    */
   loaders?: Record<string, TurboLoaderItem[]>
+
+  /**
+   * (`next --turbo` only) Additional `exports` conditions to use when resolving modules for the edge runtime.
+   */
+  edgeCustomConditions?: string[]
+
+  /**
+   * (`next --turbo` only) Server components whose source is smaller than this number of bytes are chunked with their parent instead of getting their own chunk.
+   */
+  isolatedChunkSizeThreshold?: number
+
+  /**
+   * (`next --turbo` only) Makes server components usable through `require()` by also exporting them as CommonJS.
+   */
+  serverComponentsCjsInterop?: boolean
 }
 
 export interface WebpackConfigContext {
@@ -225,6 +240,11 @@ export interface ExperimentalConfig {
   sri?: {
     algorithm?: SubresourceIntegrityAlgorithm
   }
+  /**
+   * Adds the CSP nonce of the page to the script and stylesheet tags that
+   * Turbopack inserts when it loads chunks.
+   */
+  chunkNonce?: boolean
   adjustFontFallbacks?: boolean
   adjustFontFallbacksWithSizeAdjust?: boolean
 
@@ -288,6 +308,11 @@ export interface ExperimentalConfig {
    */
   serverActionsBodySizeLimit?: SizeLimit
 
+  /**
+   * Origins, besides the app's own, that server actions can be called from.
+   */
+  serverActionsAllowedOrigins?: string[]
+
   /**
    * enables the minification of server code.
    */